@@ -0,0 +1,189 @@
+//! Attribute macros that register solutions with the runner, so adding a day needs nothing more than
+//! its `pub mod` line.
+//!
+//! ```ignore
+//! #[aoc_parse(year = 2023, day = 2)]
+//! pub fn parse(input: &str) -> eyre::Result<Vec<Game>> { ... }
+//!
+//! #[aoc(year = 2023, day = 2, part = 1)]
+//! pub fn part_one(games: &[Game]) -> eyre::Result<u64> { ... }
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{meta::ParseNestedMeta, parse_macro_input, FnArg, Item, ItemFn, LitInt, LitStr, Path, Type};
+
+#[derive(Default)]
+struct Args {
+    year: Option<LitInt>,
+    day: Option<LitInt>,
+    part: Option<LitInt>,
+    parse: Option<Path>,
+    variant: Option<LitStr>,
+}
+
+impl Args {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("year") {
+            self.year = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("day") {
+            self.day = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("part") {
+            self.part = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("parse") {
+            self.parse = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("variant") {
+            self.variant = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("expected `year`, `day`, `part`, `parse` or `variant`"));
+        }
+
+        Ok(())
+    }
+
+    fn required(value: &Option<LitInt>, name: &str) -> syn::Result<u64> {
+        value
+            .as_ref()
+            .ok_or_else(|| syn::Error::new(Span::call_site(), format!("missing `{name} = ...`")))?
+            .base10_parse()
+    }
+}
+
+/// the name of the default parser for a day, generated by `#[aoc_parse]`
+fn parser_ident(year: u64, day: u64) -> syn::Ident {
+    format_ident!("__aoc_parse_{}_{:0>2}", year, day)
+}
+
+/// does this argument take the raw input, i.e. is it a `&str`
+fn takes_input(arg: &FnArg) -> bool {
+    let FnArg::Typed(arg) = arg else {
+        return false;
+    };
+
+    match arg.ty.as_ref() {
+        Type::Reference(reference) => matches!(reference.elem.as_ref(), Type::Path(path) if path.path.is_ident("str")),
+        _ => false,
+    }
+}
+
+fn expand_aoc(args: Args, function: ItemFn) -> syn::Result<TokenStream2> {
+    let year = Args::required(&args.year, "year")?;
+    let day = Args::required(&args.day, "day")?;
+    let part = Args::required(&args.part, "part")?;
+
+    if !(1..=2).contains(&part) {
+        return Err(syn::Error::new_spanned(args.part, "part must be 1 or 2"));
+    }
+
+    // solvers that take a `&str` get the raw input, anything else goes through the day's parser
+    let ident = &function.sig.ident;
+    let call = match function.sig.inputs.first() {
+        Some(arg) if takes_input(arg) => quote!(#ident(input)),
+        _ => {
+            let parser = match args.parse {
+                Some(parser) => quote!(#parser),
+                None => {
+                    let parser = parser_ident(year, day);
+                    quote!(#parser)
+                }
+            };
+            quote!(#ident(&#parser(input)?))
+        }
+    };
+
+    let variant = match args.variant {
+        Some(variant) => quote!(::std::option::Option::Some(#variant)),
+        None => quote!(::std::option::Option::None),
+    };
+
+    let part = part as u8;
+    Ok(quote! {
+        #function
+
+        ::narigama_aoc2023::inventory::submit! {
+            ::narigama_aoc2023::runner::Solution {
+                year: #year,
+                day: #day,
+                part: #part,
+                variant: #variant,
+                function: ::std::concat!(::std::module_path!(), "::", ::std::stringify!(#ident)),
+                solver: |input| ::std::result::Result::Ok(#call?.into()),
+            }
+        }
+    })
+}
+
+fn expand_aoc_parse(args: Args, item: Item) -> syn::Result<TokenStream2> {
+    let alias = parser_ident(Args::required(&args.year, "year")?, Args::required(&args.day, "day")?);
+
+    let parser = match &item {
+        // a parser function only needs a well known name
+        Item::Fn(function) => {
+            let ident = &function.sig.ident;
+            quote! {
+                #[allow(unused_imports)]
+                use self::#ident as #alias;
+            }
+        }
+        // for a FromStr impl, parse the whole input as the implementing type
+        Item::Impl(implementation) => {
+            match &implementation.trait_ {
+                Some((None, path, _)) if path.segments.last().is_some_and(|segment| segment.ident == "FromStr") => {}
+                Some((_, path, _)) => return Err(syn::Error::new_spanned(path, "expected a `FromStr` impl")),
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        &implementation.self_ty,
+                        "expected a `FromStr` impl, not an inherent one",
+                    ))
+                }
+            }
+
+            let ty = &implementation.self_ty;
+            quote! {
+                #[allow(dead_code, clippy::needless_question_mark)]
+                fn #alias(input: &str) -> ::eyre::Result<#ty> {
+                    ::std::result::Result::Ok(<#ty as ::std::str::FromStr>::from_str(input)?)
+                }
+            }
+        }
+        item => return Err(syn::Error::new_spanned(item, "expected a function or a `FromStr` impl")),
+    };
+
+    Ok(quote! {
+        #item
+        #parser
+    })
+}
+
+/// Register a part function as the solution to `year`, `day` and `part`. Functions taking a `&str` get
+/// the raw input, anything else gets a reference to the day's parsed input, either from `#[aoc_parse]`
+/// or an explicit `parse = some_fn`. The return value only needs to convert into an `Answer`.
+///
+/// A part can have alternative implementations registered with `variant = "name"`, the runner only
+/// uses the unnamed one but `compare` checks every variant agrees with it.
+#[proc_macro_attribute]
+pub fn aoc(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = Args::default();
+    let parser = syn::meta::parser(|meta| args.parse(meta));
+    parse_macro_input!(attr with parser);
+
+    let function = parse_macro_input!(item as ItemFn);
+    expand_aoc(args, function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Mark the parser used by a day's `#[aoc]` parts, either a `fn(&str) -> eyre::Result<T>` or an
+/// `impl FromStr` whose type is built from the whole input.
+#[proc_macro_attribute]
+pub fn aoc_parse(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = Args::default();
+    let parser = syn::meta::parser(|meta| args.parse(meta));
+    parse_macro_input!(attr with parser);
+
+    let item = parse_macro_input!(item as Item);
+    expand_aoc_parse(args, item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
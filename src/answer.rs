@@ -0,0 +1,118 @@
+use std::{fmt::Display, str::FromStr};
+
+/// What a solver hands back. Most puzzles want an integer, some want a bigger one and a few want text,
+/// so everything gets converted into this for the runner and output code to deal with.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i128),
+    Unsigned(u128),
+    Text(String),
+}
+
+macro_rules! impl_from {
+    ($variant:ident, $inner:ty, $($t:ty),+) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::$variant(value as $inner)
+                }
+            }
+        )+
+    };
+}
+
+impl_from!(Signed, i128, i8, i16, i32, i64, i128, isize);
+impl_from!(Unsigned, u128, u8, u16, u32, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Signed(value) => write!(f, "{value}"),
+            Self::Unsigned(value) => write!(f, "{value}"),
+            Self::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Numbers parse as `Signed`, or `Unsigned` when they're too big for that, but only when they're written
+/// exactly as they'd print. Anything else is kept as text, so `007`, `+5` and `-0` come back unchanged.
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = s
+            .parse::<i128>()
+            .map(Self::Signed)
+            .or_else(|_| s.parse::<u128>().map(Self::Unsigned));
+
+        Ok(match number {
+            Ok(number) if number.to_string() == s => number,
+            _ => Self::Text(s.to_string()),
+        })
+    }
+}
+
+/// Numbers compare by value regardless of signedness, so `5i64` and `5u64` are the same answer.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Signed(a), Self::Signed(b)) => a == b,
+            (Self::Unsigned(a), Self::Unsigned(b)) => a == b,
+            (Self::Signed(a), Self::Unsigned(b)) | (Self::Unsigned(b), Self::Signed(a)) => {
+                u128::try_from(*a).is_ok_and(|a| a == *b)
+            }
+            (Self::Text(a), Self::Text(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+#[cfg(test)]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Answer::from(142u64), Answer::Unsigned(142));
+        assert_eq!(Answer::from(-3i64), Answer::Signed(-3));
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn test_numbers_compare_by_value() {
+        assert_eq!(Answer::from(46i64), Answer::from(46u64));
+        assert_ne!(Answer::from(-1i64), Answer::from(u128::MAX));
+        assert_ne!(Answer::from(46u64), Answer::from("46"));
+        assert_eq!("46".parse::<Answer>().unwrap(), Answer::from(46u8));
+        assert_eq!("LDR".parse::<Answer>().unwrap(), Answer::from("LDR"));
+    }
+
+    #[test]
+    fn test_text_round_trips() {
+        for text in ["007", "+5", "-0", " 46", "1_000", "LDR", ""] {
+            let answer = Answer::from(text);
+            assert_eq!(answer.to_string().parse::<Answer>().unwrap(), answer, "{text:?}");
+        }
+
+        assert_eq!("-46".parse::<Answer>().unwrap(), Answer::Signed(-46));
+        assert_eq!(
+            u128::MAX.to_string().parse::<Answer>().unwrap(),
+            Answer::Unsigned(u128::MAX)
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::{io::Write, path::PathBuf, process::ExitCode, time::Duration};
+
+use clap::{Parser, Subcommand};
+use narigama_aoc2023::{
+    progress, repl,
+    runner::{self, output::Format, DayResult, Input, Options},
+    scaffold, watch,
+};
+use tracing_chrome::ChromeLayerBuilder;
+use tracing_subscriber::{filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+#[derive(Debug, Parser)]
+#[command(about = "Advent of Code solutions")]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// write a Chrome/Perfetto compatible JSON trace of the run to this file
+    #[arg(long, env = "AOC_TRACE_FILE")]
+    trace: Option<PathBuf>,
+
+    /// how many days to solve in parallel, defaults to one per core
+    #[arg(short, long, default_value_t = 0)]
+    jobs: usize,
+
+    /// give up on a part after this many seconds
+    #[arg(short, long, env = "AOC_TIMEOUT", value_parser = parse_seconds)]
+    timeout: Option<Duration>,
+
+    /// solve each part in a child process, so it can be killed when it runs over the timeout
+    #[arg(long)]
+    isolate: bool,
+
+    /// limit each isolated child to this many MiB of memory
+    #[arg(long, requires = "isolate", value_parser = parse_mebibytes)]
+    memory_limit: Option<u64>,
+
+    /// read the input from this file instead of the cache, `-` reads it from stdin
+    #[arg(short, long, requires = "day")]
+    input: Option<PathBuf>,
+
+    /// solve one of the numbered examples instead of the real input
+    #[arg(short, long, conflicts_with = "input")]
+    example: Option<u64>,
+
+    /// print how the day worked its answer out after solving it, for days that support it
+    #[arg(long, requires = "day")]
+    explain: bool,
+
+    /// how to print the results, json and csv write a record per part to stdout
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// only solve this year
+    #[arg(short, long)]
+    year: Option<u64>,
+
+    /// only solve this day
+    #[arg(short, long)]
+    day: Option<u64>,
+
+    /// only solve this part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// solve every day and write a progress table into README.md
+    Readme {
+        #[arg(long, default_value = "README.md")]
+        path: PathBuf,
+    },
+
+    /// solve every day and print a calendar of stars and runtimes
+    Calendar,
+
+    /// generate a new day's module, bench and registration from the template
+    New {
+        #[arg(long, default_value_t = 2023)]
+        year: u64,
+        #[arg(long)]
+        day: u64,
+    },
+
+    /// run every implementation of each part, checking they agree and comparing how long they take
+    Compare {
+        /// how many times to run each implementation, slow ones stop repeating after a second
+        #[arg(long, default_value_t = 10)]
+        iterations: usize,
+    },
+
+    /// load a day's parsed input and query it interactively
+    Repl {
+        #[arg(long, default_value_t = 2023)]
+        year: u64,
+        #[arg(long)]
+        day: u64,
+    },
+
+    /// rebuild, test and solve a day whenever its source or input changes
+    Watch {
+        #[arg(long, default_value_t = 2023)]
+        year: u64,
+        #[arg(long)]
+        day: u64,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+    },
+
+    /// solve a single part with the input from stdin, used by --isolate
+    #[command(hide = true)]
+    Solve {
+        #[arg(long)]
+        year: u64,
+        #[arg(long)]
+        day: u64,
+        #[arg(long)]
+        part: u8,
+    },
+}
+
+/// a positive, finite number of seconds, which is all `Duration` can hold
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+    let seconds = value.parse::<f64>().map_err(|error| error.to_string())?;
+    match Duration::try_from_secs_f64(seconds) {
+        Ok(duration) if !duration.is_zero() => Ok(duration),
+        _ => Err(format!("{value} isn't a positive number of seconds")),
+    }
+}
+
+/// a number of MiB, as bytes so long as that still fits
+fn parse_mebibytes(value: &str) -> Result<u64, String> {
+    let mebibytes = value.parse::<u64>().map_err(|error| error.to_string())?;
+    match mebibytes.checked_mul(1024 * 1024) {
+        Some(bytes) if bytes > 0 => Ok(bytes),
+        _ => Err(format!(
+            "{value} isn't a number of MiB between 1 and {}",
+            u64::MAX >> 20
+        )),
+    }
+}
+
+fn main() -> eyre::Result<ExitCode> {
+    // load envvars and parse args
+    dotenvy::dotenv().ok();
+    let args = Args::parse();
+
+    // isolated children talk to their parent over stdout, so they have to skip logging
+    if let Some(Command::Solve { year, day, part }) = args.command {
+        let day = narigama_aoc2023::find_day(year, day)?;
+        return Ok(runner::isolate::solve_child(&day, part));
+    }
+
+    // optionally record spans to a trace file, the guard flushes it on drop so keep it around until we exit
+    let (chrome_layer, _guard) = match &args.trace {
+        Some(path) => {
+            let (layer, guard) = ChromeLayerBuilder::new().file(path).include_args(true).build();
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    // setup logging, RUST_LOG=trace will also capture spans inside hot loops
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .from_env_lossy();
+
+    // structured output owns stdout, so send the logs elsewhere
+    let fmt_layer = match args.format {
+        Format::Text => tracing_subscriber::fmt::layer().boxed(),
+        _ => tracing_subscriber::fmt::layer().with_writer(std::io::stderr).boxed(),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt_layer)
+        .with(chrome_layer)
+        .init();
+
+    let input = match (args.input, args.example) {
+        (Some(path), _) if path.as_os_str() == "-" => Input::stdin()?,
+        (Some(path), _) => Input::File(path),
+        (None, Some(example)) => Input::Example(example),
+        (None, None) => Input::Cached,
+    };
+
+    let options = Options {
+        jobs: args.jobs,
+        timeout: args.timeout,
+        isolate: args.isolate,
+        memory_limit: args.memory_limit,
+        input,
+        format: args.format,
+        year: args.year,
+        day: args.day,
+        part: args.part,
+    };
+
+    let solved = match args.command {
+        Some(Command::Readme { path }) => {
+            let results = runner::run(&narigama_aoc2023::days(), &options)?;
+            progress::update_readme(&path, &results)?;
+            tracing::info!("updated {}", path.display());
+
+            results.iter().all(DayResult::is_solved)
+        }
+        Some(Command::Calendar) => {
+            let results = runner::run(&narigama_aoc2023::days(), &options)?;
+            print!("{}", progress::calendar(&results));
+
+            results.iter().all(DayResult::is_solved)
+        }
+        Some(Command::Compare { iterations }) => {
+            let comparisons = runner::compare::compare(&narigama_aoc2023::days(), &options, iterations);
+            runner::compare::report(&comparisons)
+        }
+        Some(Command::New { year, day }) => {
+            scaffold::new_day(year, day)?;
+            true
+        }
+        Some(Command::Repl { year, day }) => {
+            repl::repl(year, day, &options.input)?;
+            true
+        }
+        Some(Command::Watch { year, day, part }) => {
+            watch::watch(year, day, part)?;
+            true
+        }
+        Some(Command::Solve { .. }) => unreachable!("handled before logging is setup"),
+        None => narigama_aoc2023::main(&options)?,
+    };
+
+    // explanations come after the results, and keep out of stdout when it's structured
+    let explained = !args.explain || explain(&options);
+
+    // any failed part, or explanation, is reflected in the exit code
+    match solved && explained {
+        true => Ok(ExitCode::SUCCESS),
+        false => Ok(ExitCode::FAILURE),
+    }
+}
+
+/// Explain each selected day that can, returning whether none of them failed.
+fn explain(options: &Options) -> bool {
+    let mut out: Box<dyn Write> = match options.format {
+        Format::Text => Box::new(std::io::stdout()),
+        _ => Box::new(std::io::stderr()),
+    };
+
+    let mut explained = true;
+    for day in narigama_aoc2023::days().iter().filter(|day| options.includes(day)) {
+        // only the days with a model, and then only the parts it knows how to explain
+        let result = match repl::inspectable(day.year, day.day) {
+            true => repl::explain(day.year, day.day, &options.parts(), &options.input, &mut out),
+            false => Ok(false),
+        };
+
+        match result {
+            Ok(true) => {}
+            Ok(false) => tracing::warn!("{}/{:0>2} has nothing to explain, skipping it", day.year, day.day),
+            Err(error) => {
+                tracing::error!("{}/{:0>2} couldn't be explained: {error:#}", day.year, day.day);
+                explained = false;
+            }
+        }
+    }
+
+    explained
+}
@@ -0,0 +1,111 @@
+//! Puzzle examples kept as plain files, `examples/{year}/{day:0>2}/{n}.txt`, next to an `answers.txt`
+//! listing what each should give as `{n} {part} {answer}` lines. Lines starting with `#` are comments.
+//! Adding an example is just a matter of dropping in the files.
+
+use std::path::PathBuf;
+
+use eyre::{Context, ContextCompat};
+
+use crate::answer::Answer;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expectation {
+    pub example: u64,
+    pub part: u8,
+    pub answer: Answer,
+}
+
+fn base_dir() -> PathBuf {
+    std::env::var("AOC_EXAMPLES_DIR")
+        .unwrap_or_else(|_| "examples".into())
+        .into()
+}
+
+/// where a day's examples and their answers live, it may not exist
+pub fn day_dir(year: u64, day: u64) -> PathBuf {
+    base_dir().join(year.to_string()).join(format!("{day:0>2}"))
+}
+
+/// the input for one of a day's examples
+pub fn load(year: u64, day: u64, example: u64) -> eyre::Result<String> {
+    let path = day_dir(year, day).join(format!("{example}.txt"));
+    std::fs::read_to_string(&path).with_context(|| format!("couldn't read {}", path.display()))
+}
+
+fn parse_answers(source: &str) -> eyre::Result<Vec<Expectation>> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let mut fields = line.splitn(3, char::is_whitespace);
+            let example = fields.next().context("missing example")?.parse()?;
+            let part = fields.next().context("missing part")?.parse()?;
+            let answer = fields.next().context("missing answer")?.trim().parse()?;
+
+            eyre::ensure!((1..=2).contains(&part), "part must be 1 or 2, not {part}");
+            Ok(Expectation { example, part, answer })
+        })
+        .collect()
+}
+
+/// what each of a day's examples should give, empty if the day has no examples
+pub fn expectations(year: u64, day: u64) -> eyre::Result<Vec<Expectation>> {
+    let path = day_dir(year, day).join("answers.txt");
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    parse_answers(&std::fs::read_to_string(&path)?).with_context(|| format!("malformed {}", path.display()))
+}
+
+/// every year and day with an examples directory, in order
+pub fn days() -> eyre::Result<Vec<(u64, u64)>> {
+    let mut days = Vec::new();
+    let base_dir = base_dir();
+    if !base_dir.is_dir() {
+        return Ok(days);
+    }
+
+    for year in std::fs::read_dir(base_dir)? {
+        let year = year?;
+        let Some(year_number) = year.file_name().to_str().and_then(|name| name.parse().ok()) else {
+            continue;
+        };
+
+        for day in std::fs::read_dir(year.path())? {
+            if let Some(day_number) = day?.file_name().to_str().and_then(|name| name.parse().ok()) {
+                days.push((year_number, day_number));
+            }
+        }
+    }
+
+    days.sort();
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_answers, Expectation};
+
+    #[test]
+    fn test_parse_answers() {
+        let source = "# example part answer\n1 1 35\n\n2 2 some text\n";
+        let expected = vec![
+            Expectation {
+                example: 1,
+                part: 1,
+                answer: 35u64.into(),
+            },
+            Expectation {
+                example: 2,
+                part: 2,
+                answer: "some text".into(),
+            },
+        ];
+
+        assert_eq!(parse_answers(source).unwrap(), expected);
+        assert!(parse_answers("1 3 35").is_err());
+        assert!(parse_answers("1 1").is_err());
+    }
+}
@@ -1,6 +1,40 @@
+//! Shared infrastructure, fetching inputs, running and reporting on solutions, plus a module per AoC
+//! year holding that year's solutions. Solutions register themselves with `#[aoc(...)]`.
+
+// lets the macros name this crate the same way from inside it as from outside
+extern crate self as narigama_aoc2023;
+
+#[doc(hidden)]
+pub use inventory;
+pub use narigama_aoc_macros::{aoc, aoc_parse};
+use runner::Day;
+
+pub mod answer;
+pub mod examples;
+pub mod progress;
+pub mod repl;
+pub mod runner;
+pub mod scaffold;
 pub mod util;
+pub mod watch;
+
 pub mod year2023;
 
-pub fn main() -> eyre::Result<()> {
-    year2023::main()
+/// every registered day, in year then day order
+pub fn days() -> Vec<Day> {
+    Day::registered()
+}
+
+pub fn find_day(year: u64, day: u64) -> eyre::Result<Day> {
+    days()
+        .into_iter()
+        .find(|d| d.year == year && d.day == day)
+        .ok_or_else(|| eyre::eyre!("{year}/{day:0>2} has no solution"))
+}
+
+/// Solve every registered day matching the options, returns false if any part failed.
+pub fn main(options: &runner::Options) -> eyre::Result<bool> {
+    let results = runner::run(&days(), options)?;
+
+    runner::report(&results, options.format)
 }
@@ -0,0 +1,188 @@
+use std::{collections::BTreeSet, fmt::Write, path::Path, time::Duration};
+
+use crate::runner::{DayResult, PartResult};
+
+pub const START_MARKER: &str = "<!-- progress:start -->";
+pub const END_MARKER: &str = "<!-- progress:end -->";
+
+fn stars(result: &DayResult) -> usize {
+    result.parts.iter().filter(|part| part.outcome.is_solved()).count()
+}
+
+fn runtime(part: Option<&PartResult>) -> String {
+    match part {
+        Some(part) if part.outcome.is_solved() => format!("{:.2?}", part.elapsed),
+        Some(part) => part.outcome.status().to_string(),
+        None => "-".into(),
+    }
+}
+
+/// Markdown table of stars and runtimes, linking each day to its source module.
+pub fn markdown_table(results: &[DayResult]) -> String {
+    let mut table = String::from("| Year | Day | Stars | Part One | Part Two | Source |\n|---|---|---|---|---|---|\n");
+
+    for result in results {
+        let module = format!("day{:0>2}.rs", result.day);
+
+        writeln!(
+            table,
+            "| {} | {} | {} | {} | {} | [{module}](src/year{}/{module}) |",
+            result.year,
+            result.day,
+            "⭐".repeat(stars(result)),
+            runtime(result.part(1)),
+            runtime(result.part(2)),
+            result.year,
+        )
+        .expect("writing to a String can't fail");
+    }
+
+    let total = results.iter().map(stars).sum::<usize>();
+    let elapsed = results.iter().map(|result| result.elapsed).sum::<Duration>();
+    write!(table, "\n**{total}** stars in {elapsed:.2?}\n").expect("writing to a String can't fail");
+
+    table
+}
+
+/// Swap whatever is between the progress markers in `readme` for a fresh table.
+pub fn update_readme(path: impl AsRef<Path>, results: &[DayResult]) -> eyre::Result<()> {
+    let path = path.as_ref();
+    let readme = std::fs::read_to_string(path)?;
+
+    let (head, rest) = readme
+        .split_once(START_MARKER)
+        .ok_or_else(|| eyre::eyre!("{} is missing `{START_MARKER}`", path.display()))?;
+    let (_, tail) = rest
+        .split_once(END_MARKER)
+        .ok_or_else(|| eyre::eyre!("{} is missing `{END_MARKER}`", path.display()))?;
+
+    let table = markdown_table(results);
+    std::fs::write(path, format!("{head}{START_MARKER}\n{table}{END_MARKER}{tail}"))?;
+
+    Ok(())
+}
+
+/// An advent calendar per year for the terminal, five days to a row with a star per solved part and how
+/// long the day took.
+pub fn calendar(results: &[DayResult]) -> String {
+    let mut calendar = String::new();
+    let years = results.iter().map(|result| result.year).collect::<BTreeSet<_>>();
+
+    for year in years {
+        writeln!(calendar, "{year}").expect("writing to a String can't fail");
+
+        for week in (1..=25).collect::<Vec<u64>>().chunks(5) {
+            for day in week {
+                let cell = match results.iter().find(|result| result.year == year && result.day == *day) {
+                    Some(result) => format!(
+                        "{:<2} {:>7}",
+                        "*".repeat(stars(result)),
+                        format!("{:.1?}", result.elapsed)
+                    ),
+                    None => " ".repeat(10),
+                };
+                write!(calendar, " {day:>2} {cell} ").expect("writing to a String can't fail");
+            }
+            calendar.push('\n');
+        }
+    }
+
+    calendar
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::runner::{DayResult, Outcome, PartResult};
+
+    fn results() -> Vec<DayResult> {
+        let part = |part, outcome, millis| PartResult {
+            part,
+            outcome,
+            elapsed: Duration::from_millis(millis),
+        };
+        let day = |day, parts, millis| DayResult {
+            year: 2023,
+            day,
+            parts,
+            elapsed: Duration::from_millis(millis),
+            input_hash: None,
+        };
+
+        vec![
+            day(
+                1,
+                vec![
+                    part(1, Outcome::Solved(1u64.into()), 1),
+                    part(2, Outcome::Solved(2u64.into()), 2),
+                ],
+                2,
+            ),
+            day(7, vec![part(1, Outcome::TimedOut, 30)], 30),
+        ]
+    }
+
+    #[test]
+    fn test_markdown_table() {
+        assert_eq!(
+            super::markdown_table(&results()),
+            "| Year | Day | Stars | Part One | Part Two | Source |\n\
+             |---|---|---|---|---|---|\n\
+             | 2023 | 1 | ⭐⭐ | 1.00ms | 2.00ms | [day01.rs](src/year2023/day01.rs) |\n\
+             | 2023 | 7 |  | timeout | - | [day07.rs](src/year2023/day07.rs) |\n\
+             \n\
+             **2** stars in 32.00ms\n"
+        );
+    }
+
+    #[test]
+    fn test_update_readme() {
+        let path = std::env::temp_dir().join(format!("aoc-progress-{}.md", std::process::id()));
+        let update = |readme: &str| {
+            std::fs::write(&path, readme).unwrap();
+            super::update_readme(&path, &results()).map(|_| std::fs::read_to_string(&path).unwrap())
+        };
+
+        let readme = update("# AoC\n<!-- progress:start -->\nstale\n<!-- progress:end -->\nmore\n").unwrap();
+        assert!(readme.starts_with("# AoC\n<!-- progress:start -->\n| Year |"));
+        assert!(readme.ends_with("in 32.00ms\n<!-- progress:end -->\nmore\n"));
+        assert!(!readme.contains("stale"));
+
+        let error = update("# AoC\n<!-- progress:end -->\n").unwrap_err();
+        assert!(
+            error.to_string().ends_with("is missing `<!-- progress:start -->`"),
+            "{error}"
+        );
+
+        let error = update("# AoC\n<!-- progress:start -->\n").unwrap_err();
+        assert!(
+            error.to_string().ends_with("is missing `<!-- progress:end -->`"),
+            "{error}"
+        );
+
+        // a failed update leaves the file alone
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# AoC\n<!-- progress:start -->\n"
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_calendar() {
+        let calendar = super::calendar(&results());
+        let lines = calendar.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "2023");
+        assert_eq!(
+            lines[1].trim_end(),
+            "  1 **   2.0ms   2              3              4              5"
+        );
+        assert_eq!(
+            lines[2].trim_end(),
+            "  6              7     30.0ms   8              9             10"
+        );
+    }
+}
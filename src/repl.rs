@@ -0,0 +1,135 @@
+//! Poke at a day's parsed puzzle without sprinkling `dbg!` calls around. A day opts in by implementing
+//! [`Inspect`] for its model and submitting a [`Model`] that parses the input into it.
+
+use std::io::{BufRead, Write};
+
+use crate::runner::Input;
+
+/// A parsed puzzle that can answer questions about itself.
+pub trait Inspect {
+    /// `(usage, description)` for each command, shown by `help`
+    fn commands(&self) -> Vec<(&'static str, &'static str)>;
+
+    /// run a command, `None` if it isn't one this model knows
+    fn run(&self, command: &str, args: &[&str]) -> Option<eyre::Result<String>>;
+
+    /// walk through how a part gets its answer, `None` if this model can't
+    fn explain(&self, _part: u8) -> Option<eyre::Result<String>> {
+        None
+    }
+}
+
+/// Parses a day's input into something the REPL can inspect, registered with `inventory::submit!`.
+pub struct Model {
+    pub year: u64,
+    pub day: u64,
+    pub load: fn(&str) -> eyre::Result<Box<dyn Inspect>>,
+}
+
+inventory::collect!(Model);
+
+/// parse the argument at `index`, naming it in the error if it's missing or malformed
+pub fn arg<T>(args: &[&str], index: usize, name: &str) -> eyre::Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let raw = args.get(index).ok_or_else(|| eyre::eyre!("missing <{name}>"))?;
+    raw.parse()
+        .map_err(|error| eyre::eyre!("bad <{name}> `{raw}`: {error}"))
+}
+
+fn model(year: u64, day: u64) -> Option<&'static Model> {
+    inventory::iter::<Model>
+        .into_iter()
+        .find(|model| model.year == year && model.day == day)
+}
+
+/// whether a day has submitted a [`Model`]
+pub fn inspectable(year: u64, day: u64) -> bool {
+    model(year, day).is_some()
+}
+
+/// load a day's model from its input
+fn load(year: u64, day: u64, input: &Input) -> eyre::Result<Box<dyn Inspect>> {
+    let loader = model(year, day).ok_or_else(|| eyre::eyre!("{year}/{day:0>2} has nothing to inspect"))?;
+
+    (loader.load)(&input.load(year, day)?)
+}
+
+fn help(model: &dyn Inspect) -> String {
+    let mut commands = model.commands();
+    commands.extend([("help", "show this list"), ("quit", "leave, as does ctrl-d")]);
+
+    let width = commands.iter().map(|(usage, _)| usage.len()).max().unwrap_or(0);
+    commands
+        .into_iter()
+        .map(|(usage, description)| format!("  {usage:<width$}  {description}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Load a day's model, then read commands from stdin until `quit` or the end of input.
+pub fn repl(year: u64, day: u64, input: &Input) -> eyre::Result<()> {
+    let model = load(year, day, input)?;
+    println!("loaded {year}/{day:0>2}, `help` lists the commands");
+
+    let mut lines = std::io::stdin().lock().lines();
+    loop {
+        print!("{year}/{day:0>2}> ");
+        std::io::stdout().flush()?;
+
+        let Some(line) = lines.next().transpose()? else {
+            println!();
+            return Ok(());
+        };
+
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            continue;
+        };
+        let args = words.collect::<Vec<_>>();
+
+        match command {
+            "help" => println!("{}", help(model.as_ref())),
+            "quit" | "exit" => return Ok(()),
+            command => match model.run(command, &args) {
+                Some(Ok(output)) => println!("{output}"),
+                Some(Err(error)) => println!("error: {error:#}"),
+                None => println!("unknown command `{command}`, try `help`"),
+            },
+        }
+    }
+}
+
+/// Write how each of the parts gets its answer to `out`, skipping any the day can't explain. Returns whether it
+/// explained any of them.
+pub fn explain(year: u64, day: u64, parts: &[u8], input: &Input, out: &mut dyn Write) -> eyre::Result<bool> {
+    let model = load(year, day, input)?;
+
+    let mut explained = false;
+    for part in parts {
+        if let Some(explanation) = model.explain(*part) {
+            writeln!(out, "{year}/{day:0>2} part {part}\n{}", explanation?)?;
+            explained = true;
+        }
+    }
+
+    Ok(explained)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_arg() {
+        let args = ["79", "x"];
+
+        assert_eq!(super::arg::<i64>(&args, 0, "seed").unwrap(), 79);
+
+        let error = super::arg::<i64>(&args, 1, "seed").unwrap_err();
+        assert_eq!(error.to_string(), "bad <seed> `x`: invalid digit found in string");
+
+        let error = super::arg::<i64>(&args, 2, "seed").unwrap_err();
+        assert_eq!(error.to_string(), "missing <seed>");
+    }
+}
@@ -0,0 +1,44 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+thread_local! {
+    static CURRENT: RefCell<CancellationToken> = RefCell::new(CancellationToken::default());
+}
+
+/// Cooperative cancellation for solvers. The runner hands each part a fresh token and cancels it when the
+/// part runs over its time budget, long running loops should call [`check_cancelled`] to notice.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// make this the token seen by [`current`] and [`check_cancelled`] on this thread
+    pub fn install(&self) {
+        CURRENT.with(|current| current.replace(self.clone()));
+    }
+}
+
+/// the token for the part running on this thread, clone it to share with any threads the solver spawns
+pub fn current() -> CancellationToken {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// bail out if the part running on this thread has been cancelled
+pub fn check_cancelled() -> eyre::Result<()> {
+    match CURRENT.with(|current| current.borrow().is_cancelled()) {
+        true => eyre::bail!("cancelled"),
+        false => Ok(()),
+    }
+}
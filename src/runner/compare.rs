@@ -0,0 +1,260 @@
+//! Run every implementation of a part against the same input, check they agree with each other and see
+//! how their timings stack up. Days run one after another, so the timings don't fight over cores.
+
+use std::{
+    sync::{mpsc, Arc},
+    time::{Duration, Instant},
+};
+
+use super::{part_name, solve, Day, Options, Outcome, Solution, Worker};
+
+/// stop repeating a variant once it has used this much time, slow brute forces only need to run once
+const BUDGET: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+pub struct VariantResult {
+    pub name: &'static str,
+    /// the outcome of the first run, repeats only add timings
+    pub outcome: Outcome,
+    pub timings: Vec<Duration>,
+}
+
+impl VariantResult {
+    pub fn median(&self) -> Option<Duration> {
+        let mut timings = self.timings.clone();
+        timings.sort();
+        timings.get(timings.len() / 2).copied()
+    }
+
+    pub fn fastest(&self) -> Option<Duration> {
+        self.timings.iter().min().copied()
+    }
+}
+
+#[derive(Debug)]
+pub struct PartComparison {
+    pub year: u64,
+    pub day: u64,
+    pub part: u8,
+    /// the main implementation first, then the variants by name
+    pub variants: Vec<VariantResult>,
+}
+
+impl PartComparison {
+    /// do all the variants that finished agree on the answer, timeouts don't count against this
+    pub fn agrees(&self) -> bool {
+        let mut answers = self.variants.iter().filter_map(|variant| match &variant.outcome {
+            Outcome::Solved(answer) => Some(answer),
+            _ => None,
+        });
+
+        match answers.next() {
+            Some(first) => answers.all(|answer| answer == first),
+            None => true,
+        }
+    }
+
+    /// every variant either agreed or ran out of time, none of them failed outright
+    pub fn is_ok(&self) -> bool {
+        self.agrees()
+            && self
+                .variants
+                .iter()
+                .all(|variant| matches!(variant.outcome, Outcome::Solved(_) | Outcome::TimedOut))
+    }
+}
+
+/// Solve a part up to `iterations` times on a [`Worker`], timing each run. The timeout applies to each
+/// run, and the first run that doesn't solve the part stops the repeats.
+fn run_variant(solution: &Solution, input: Arc<str>, iterations: usize, timeout: Option<Duration>) -> VariantResult {
+    let solver = solution.solver;
+    let worker = Worker::spawn(move |sender| {
+        let mut used = Duration::ZERO;
+        for _ in 0..iterations.max(1) {
+            let start = Instant::now();
+            let outcome = solve(solver, &input);
+            let elapsed = start.elapsed();
+            used += elapsed;
+
+            let done = !outcome.is_solved() || used >= BUDGET;
+            if sender.send((outcome, elapsed)).is_err() || done {
+                break;
+            }
+        }
+    });
+
+    let mut result = VariantResult {
+        name: solution.name(),
+        outcome: Outcome::TimedOut,
+        timings: Vec::new(),
+    };
+
+    let worker = match worker {
+        Ok(worker) => worker,
+        Err(error) => {
+            result.outcome = Outcome::Failed(format!("couldn't spawn solver thread: {error}"));
+            return result;
+        }
+    };
+
+    loop {
+        match worker.recv(timeout) {
+            Ok((outcome, elapsed)) => {
+                if result.timings.is_empty() {
+                    result.outcome = outcome;
+                }
+                result.timings.push(elapsed);
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => break,
+            Err(mpsc::RecvTimeoutError::Disconnected) if result.timings.is_empty() => {
+                result.outcome = Outcome::Failed("solver thread exited without an answer".into());
+                break;
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    result
+}
+
+/// Run every implementation of every part the options select, `iterations` times each. A day whose
+/// input can't be loaded has every variant marked as failed, the rest still get compared.
+pub fn compare(days: &[Day], options: &Options, iterations: usize) -> Vec<PartComparison> {
+    let mut comparisons = Vec::new();
+
+    for day in days.iter().filter(|day| options.includes(day)) {
+        let span = tracing::info_span!("day", year = day.year, day = day.day);
+        let input = span
+            .in_scope(|| options.input.load(day.year, day.day))
+            .map(Arc::<str>::from);
+
+        for part in options.parts() {
+            let variants = day
+                .variants(part)
+                .into_iter()
+                .map(|solution| match &input {
+                    Ok(input) => span.in_scope(|| run_variant(solution, input.clone(), iterations, options.timeout)),
+                    Err(error) => VariantResult {
+                        name: solution.name(),
+                        outcome: Outcome::Failed(format!("{error:#}")),
+                        timings: Vec::new(),
+                    },
+                })
+                .collect::<Vec<_>>();
+
+            if !variants.is_empty() {
+                comparisons.push(PartComparison {
+                    year: day.year,
+                    day: day.day,
+                    part,
+                    variants,
+                });
+            }
+        }
+    }
+
+    comparisons
+}
+
+/// Log each variant's answer and timings relative to the first, then whether they agreed. Returns true
+/// if no part had variants that disagreed or failed.
+pub fn report(comparisons: &[PartComparison]) -> bool {
+    for comparison in comparisons {
+        let prefix = format!(
+            "{}/{:0>2} {}",
+            comparison.year,
+            comparison.day,
+            part_name(comparison.part)
+        );
+        let baseline = comparison.variants.first().and_then(VariantResult::median);
+
+        for variant in comparison.variants.iter() {
+            let timing = match (variant.median(), variant.fastest()) {
+                (Some(median), Some(fastest)) => {
+                    let relative = match baseline {
+                        Some(baseline) if !baseline.is_zero() => {
+                            format!(", {:.2}x", median.as_secs_f64() / baseline.as_secs_f64())
+                        }
+                        _ => String::new(),
+                    };
+                    format!(
+                        "median {median:.2?}, fastest {fastest:.2?} over {} runs{relative}",
+                        variant.timings.len()
+                    )
+                }
+                _ => "no runs finished".into(),
+            };
+
+            match variant.outcome {
+                Outcome::Solved(_) => tracing::info!("{prefix} {}: {} ({timing})", variant.name, variant.outcome),
+                Outcome::TimedOut => tracing::warn!("{prefix} {}: {} ({timing})", variant.name, variant.outcome),
+                _ => tracing::error!("{prefix} {}: {} ({timing})", variant.name, variant.outcome),
+            }
+        }
+
+        if !comparison.agrees() {
+            tracing::error!("{prefix}: variants disagree");
+        }
+    }
+
+    let failed = comparisons.iter().filter(|comparison| !comparison.is_ok()).count();
+    match failed {
+        0 => tracing::info!("compared {} parts, every variant agrees", comparisons.len()),
+        _ => tracing::error!(
+            "compared {} parts, {failed} had variants disagree or fail",
+            comparisons.len()
+        ),
+    }
+
+    failed == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{run_variant, PartComparison};
+    use crate::runner::{Outcome, Solution};
+
+    fn solution(variant: Option<&'static str>, solver: crate::runner::Solver) -> Solution {
+        Solution {
+            year: 2023,
+            day: 1,
+            part: 1,
+            variant,
+            function: "solution",
+            solver,
+        }
+    }
+
+    #[test]
+    fn test_variants_are_compared() {
+        let main = solution(None, |input| Ok(input.len().into()));
+        let counted = solution(Some("counted"), |input| Ok(input.chars().count().into()));
+        let wrong = solution(Some("wrong"), |_| Ok(0u64.into()));
+        let stuck = solution(Some("stuck"), |_| loop {
+            crate::runner::check_cancelled()?;
+            std::thread::sleep(Duration::from_millis(1));
+        });
+
+        let run = |solutions: &[&Solution]| PartComparison {
+            year: 2023,
+            day: 1,
+            part: 1,
+            variants: solutions
+                .iter()
+                .map(|solution| run_variant(solution, "abc".into(), 3, Some(Duration::from_millis(20))))
+                .collect(),
+        };
+
+        let agreed = run(&[&main, &counted, &stuck]);
+        assert_eq!(agreed.variants[0].outcome, Outcome::Solved(3u64.into()));
+        assert_eq!(agreed.variants[0].timings.len(), 3);
+        assert_eq!(agreed.variants[2].outcome, Outcome::TimedOut);
+        assert!(agreed.is_ok());
+
+        let disagreed = run(&[&main, &wrong]);
+        assert!(!disagreed.agrees());
+        assert!(!disagreed.is_ok());
+    }
+}
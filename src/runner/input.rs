@@ -0,0 +1,36 @@
+use std::{path::PathBuf, sync::Arc};
+
+use eyre::Context;
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, Default)]
+pub enum Input {
+    /// the real input, fetched and cached by [`crate::util::get_input`]
+    #[default]
+    Cached,
+    /// an arbitrary file, handy for edge cases
+    File(PathBuf),
+    /// input that has already been read, e.g. from stdin
+    Text(Arc<str>),
+    /// one of the day's numbered examples, see [`crate::examples`]
+    Example(u64),
+}
+
+impl Input {
+    /// read the whole of stdin, so every day that runs sees the same text
+    pub fn stdin() -> eyre::Result<Self> {
+        let input = std::io::read_to_string(std::io::stdin()).context("couldn't read stdin")?;
+        Ok(Self::Text(input.into()))
+    }
+
+    pub fn load(&self, year: u64, day: u64) -> eyre::Result<String> {
+        match self {
+            Self::Cached => crate::util::get_input(year, day),
+            Self::File(path) => {
+                std::fs::read_to_string(path).with_context(|| format!("couldn't read {}", path.display()))
+            }
+            Self::Text(input) => Ok(input.to_string()),
+            Self::Example(example) => crate::examples::load(year, day, *example),
+        }
+    }
+}
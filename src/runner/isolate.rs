@@ -0,0 +1,136 @@
+//! Hard-kill fallback for solvers that never check their cancellation token. Each part is solved by a
+//! child copy of the current executable, which must handle `solve --year Y --day D --part P` by calling
+//! [`solve_child`]. The child reads the input from stdin and prints the answer to stdout.
+
+use std::{
+    io::{Read, Write},
+    panic::AssertUnwindSafe,
+    process::{Command, ExitCode, Stdio},
+    time::{Duration, Instant},
+};
+
+use super::{Day, Options, Outcome};
+
+/// how often to check on a running child
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// the same exit code rust uses when the main thread panics
+const PANIC_EXIT_CODE: u8 = 101;
+
+/// Child side: read the input from stdin, solve the part and print the answer. Errors and panics are
+/// written to stderr as a single message for the parent to pick up.
+pub fn solve_child(day: &Day, part: u8) -> ExitCode {
+    // we report panics ourselves, so keep the default hook's noise out of stderr
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let solve = || {
+        let solver = day.solver(part)?;
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+
+        solver(&input)
+    };
+
+    match std::panic::catch_unwind(AssertUnwindSafe(solve)) {
+        Ok(Ok(answer)) => {
+            println!("{answer}");
+            ExitCode::SUCCESS
+        }
+        Ok(Err(error)) => {
+            eprintln!("{error:#}");
+            ExitCode::FAILURE
+        }
+        Err(payload) => {
+            eprintln!("{}", super::panic_message(payload.as_ref()));
+            ExitCode::from(PANIC_EXIT_CODE)
+        }
+    }
+}
+
+#[cfg(unix)]
+fn limit_memory(command: &mut Command, bytes: u64) {
+    use std::os::unix::process::CommandExt;
+
+    // SAFETY: setrlimit is async-signal-safe and the closure doesn't allocate, so it's fine to call
+    // between fork and exec.
+    unsafe {
+        command.pre_exec(move || {
+            let limit = libc::rlimit {
+                rlim_cur: bytes as _,
+                rlim_max: bytes as _,
+            };
+
+            match libc::setrlimit(libc::RLIMIT_AS, &limit) {
+                0 => Ok(()),
+                _ => Err(std::io::Error::last_os_error()),
+            }
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_command: &mut Command, _bytes: u64) {
+    tracing::warn!("memory limits are only supported on unix, running without one");
+}
+
+/// Parent side: solve a part in a child process, killing it if it runs over the time budget.
+pub fn run_part(day: &Day, part: u8, input: &str, options: &Options) -> eyre::Result<Outcome> {
+    let mut command = Command::new(std::env::current_exe()?);
+    command
+        .args(["solve", "--year", &day.year.to_string(), "--day", &day.day.to_string()])
+        .args(["--part", &part.to_string()])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    if let Some(bytes) = options.memory_limit {
+        limit_memory(&mut command, bytes);
+    }
+
+    let mut child = command.spawn()?;
+
+    // feed the input and drain the output on their own threads, so a full pipe can't wedge either side
+    let mut stdin = child.stdin.take().ok_or_else(|| eyre::eyre!("child has no stdin"))?;
+    let mut stdout = child.stdout.take().ok_or_else(|| eyre::eyre!("child has no stdout"))?;
+    let mut stderr = child.stderr.take().ok_or_else(|| eyre::eyre!("child has no stderr"))?;
+
+    let input = input.to_owned();
+    std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let stdout = std::thread::spawn(move || {
+        let mut buffer = String::new();
+        stdout.read_to_string(&mut buffer).map(|_| buffer)
+    });
+    let stderr = std::thread::spawn(move || {
+        let mut buffer = String::new();
+        stderr.read_to_string(&mut buffer).map(|_| buffer)
+    });
+
+    // wait for the child to finish, or run out of patience and kill it
+    let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            child.kill()?;
+            child.wait()?;
+            return Ok(Outcome::TimedOut);
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    };
+
+    let stdout = stdout.join().map_err(|_| eyre::eyre!("stdout reader panicked"))??;
+    let stderr = stderr.join().map_err(|_| eyre::eyre!("stderr reader panicked"))??;
+    let message = stderr.trim().to_string();
+
+    Ok(match status.code() {
+        // only strip the newline println added, a text answer's own whitespace is part of it
+        Some(0) => Outcome::Solved(stdout.strip_suffix('\n').unwrap_or(&stdout).parse()?),
+        Some(code) if code == PANIC_EXIT_CODE as i32 => Outcome::Panicked(message),
+        Some(_) => Outcome::Failed(message),
+        // killed by a signal, most likely an abort after hitting the memory limit
+        None => Outcome::Failed(format!("child {status}, {message}")),
+    })
+}
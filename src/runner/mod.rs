@@ -0,0 +1,485 @@
+use std::{
+    panic::AssertUnwindSafe,
+    sync::{mpsc, Arc},
+    time::{Duration, Instant},
+};
+
+use itertools::Itertools;
+use rayon::prelude::*;
+
+use crate::answer::Answer;
+
+pub mod cancel;
+pub mod compare;
+mod input;
+pub mod isolate;
+pub mod output;
+
+pub use cancel::{check_cancelled, CancellationToken};
+pub use input::Input;
+
+/// Takes the raw puzzle input, parses it and returns the answer for a single part. Each part parses
+/// the input for itself, so the two can run side by side without sharing anything and can use different
+/// parsers, at the cost of parsing twice per day.
+pub type Solver = fn(&str) -> eyre::Result<Answer>;
+
+/// A solver for a single part, registered with `#[aoc(...)]` and collected at startup.
+#[derive(Debug)]
+pub struct Solution {
+    pub year: u64,
+    pub day: u64,
+    pub part: u8,
+    /// alternative implementations are named, the one the runner uses isn't
+    pub variant: Option<&'static str>,
+    /// the path of the registered function, for telling clashing registrations apart
+    pub function: &'static str,
+    pub solver: Solver,
+}
+
+impl Solution {
+    pub fn name(&self) -> &'static str {
+        self.variant.unwrap_or("main")
+    }
+}
+
+inventory::collect!(Solution);
+
+/// Everything the runner needs to know to solve a single day.
+#[derive(Debug, Clone)]
+pub struct Day {
+    pub year: u64,
+    pub day: u64,
+    pub solutions: Vec<&'static Solution>,
+}
+
+impl Day {
+    /// every registered solution, grouped by day in year then day order
+    pub fn registered() -> Vec<Day> {
+        Self::group(inventory::iter::<Solution>.into_iter().collect())
+    }
+
+    /// Group solutions by day. Two solutions for the same part and variant would leave it up to the sort
+    /// order which one runs, so that panics naming both.
+    fn group(mut solutions: Vec<&'static Solution>) -> Vec<Day> {
+        solutions.sort_by_key(|s| (s.year, s.day, s.part, s.variant, s.function));
+
+        if let Some((a, b)) = solutions
+            .iter()
+            .tuple_windows()
+            .find(|(a, b)| (a.year, a.day, a.part, a.variant) == (b.year, b.day, b.part, b.variant))
+        {
+            panic!(
+                "{} and {} are both registered as {}/{:0>2} part {} ({})",
+                a.function,
+                b.function,
+                a.year,
+                a.day,
+                a.part,
+                a.name()
+            );
+        }
+
+        let mut days = Vec::<Day>::new();
+        for solution in solutions {
+            match days.last_mut() {
+                Some(day) if day.year == solution.year && day.day == solution.day => day.solutions.push(solution),
+                _ => days.push(Day {
+                    year: solution.year,
+                    day: solution.day,
+                    solutions: vec![solution],
+                }),
+            }
+        }
+
+        days
+    }
+
+    pub fn solver(&self, part: u8) -> eyre::Result<Solver> {
+        self.solutions
+            .iter()
+            .find(|solution| solution.part == part && solution.variant.is_none())
+            .map(|solution| solution.solver)
+            .ok_or_else(|| eyre::eyre!("{}/{:0>2} has no solution for part {part}", self.year, self.day))
+    }
+
+    pub fn has_part(&self, part: u8) -> bool {
+        self.solutions
+            .iter()
+            .any(|solution| solution.part == part && solution.variant.is_none())
+    }
+
+    /// every implementation of a part, the main one first then the variants by name
+    pub fn variants(&self, part: u8) -> Vec<&'static Solution> {
+        self.solutions
+            .iter()
+            .filter(|solution| solution.part == part)
+            .copied()
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// how many days to run at once, 0 uses one thread per core
+    pub jobs: usize,
+    /// how long a single part may run before it's reported as timed out
+    pub timeout: Option<Duration>,
+    /// solve each part in a child process that gets killed when it runs over `timeout`
+    pub isolate: bool,
+    /// address space limit in bytes for isolated children
+    pub memory_limit: Option<u64>,
+    /// where to read each day's input from
+    pub input: Input,
+    /// how to report the results once every day has run
+    pub format: output::Format,
+    /// only run this year
+    pub year: Option<u64>,
+    /// only run this day
+    pub day: Option<u64>,
+    /// only run this part
+    pub part: Option<u8>,
+}
+
+impl Options {
+    pub fn parts(&self) -> Vec<u8> {
+        [1, 2]
+            .into_iter()
+            .filter(|part| self.part.is_none_or(|p| p == *part))
+            .collect()
+    }
+
+    /// does the year and day filter let this day through
+    pub fn includes(&self, day: &Day) -> bool {
+        self.year.is_none_or(|y| y == day.year) && self.day.is_none_or(|d| d == day.day)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(Answer),
+    Failed(String),
+    Panicked(String),
+    TimedOut,
+}
+
+impl Outcome {
+    pub fn is_solved(&self) -> bool {
+        matches!(self, Self::Solved(_))
+    }
+
+    /// a short machine friendly name for the outcome
+    pub fn status(&self) -> &'static str {
+        match self {
+            Self::Solved(_) => "ok",
+            Self::Failed(_) => "error",
+            Self::Panicked(_) => "panic",
+            Self::TimedOut => "timeout",
+        }
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Solved(answer) => write!(f, "{answer}"),
+            Self::Failed(error) => write!(f, "failed: {error}"),
+            Self::Panicked(message) => write!(f, "panicked: {message}"),
+            Self::TimedOut => write!(f, "timed out"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: u8,
+    pub outcome: Outcome,
+    /// how long the part took, including parsing the input, as every part parses its own copy
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct DayResult {
+    pub year: u64,
+    pub day: u64,
+    pub parts: Vec<PartResult>,
+    /// wall time for all the parts, this is less than their sum when they ran side by side
+    pub elapsed: Duration,
+    /// see [`crate::util::hash_input`], missing if the input couldn't be loaded
+    pub input_hash: Option<String>,
+}
+
+impl DayResult {
+    pub fn part(&self, part: u8) -> Option<&PartResult> {
+        self.parts.iter().find(|result| result.part == part)
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.parts.iter().all(|part| part.outcome.is_solved())
+    }
+}
+
+fn part_name(part: u8) -> &'static str {
+    match part {
+        1 => "Part One",
+        _ => "Part Two",
+    }
+}
+
+/// pull the message out of a panic payload, these are almost always a `&str` or a `String`
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".into()
+    }
+}
+
+fn solve(solver: Solver, input: &str) -> Outcome {
+    // a bad day shouldn't take the rest of the run down with it
+    match std::panic::catch_unwind(AssertUnwindSafe(|| solver(input))) {
+        Ok(Ok(answer)) => Outcome::Solved(answer),
+        Ok(Err(error)) => Outcome::Failed(format!("{error:#}")),
+        Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+    }
+}
+
+/// Work running on a thread of its own, so we can stop waiting on it once it runs over the time budget.
+/// The thread can't be killed, instead its cancellation token is set and it's left to notice on its own.
+struct Worker<T> {
+    token: CancellationToken,
+    receiver: mpsc::Receiver<T>,
+}
+
+impl<T: Send + 'static> Worker<T> {
+    /// start `work` with a fresh cancellation token, and the current span so its logs stay in place
+    fn spawn(work: impl FnOnce(mpsc::Sender<T>) + Send + 'static) -> std::io::Result<Self> {
+        let token = CancellationToken::default();
+        let (sender, receiver) = mpsc::channel();
+        let span = tracing::Span::current();
+
+        std::thread::Builder::new().spawn({
+            let token = token.clone();
+            move || {
+                let _span = span.entered();
+                token.install();
+                work(sender);
+            }
+        })?;
+
+        Ok(Self { token, receiver })
+    }
+
+    /// wait for the next thing the work sends, cancelling it if that takes longer than `timeout`
+    fn recv(&self, timeout: Option<Duration>) -> Result<T, mpsc::RecvTimeoutError> {
+        let received = match timeout {
+            Some(timeout) => self.receiver.recv_timeout(timeout),
+            None => self.receiver.recv().map_err(mpsc::RecvTimeoutError::from),
+        };
+
+        if let Err(mpsc::RecvTimeoutError::Timeout) = received {
+            self.token.cancel();
+        }
+        received
+    }
+}
+
+/// solve on a [`Worker`], giving up once it runs over the time budget
+fn run_part(solver: Solver, input: Arc<str>, timeout: Option<Duration>) -> Outcome {
+    let worker = Worker::spawn(move |sender| {
+        sender.send(solve(solver, &input)).ok();
+    });
+
+    match worker.map(|worker| worker.recv(timeout)) {
+        Ok(Ok(outcome)) => outcome,
+        Ok(Err(mpsc::RecvTimeoutError::Timeout)) => Outcome::TimedOut,
+        Ok(Err(mpsc::RecvTimeoutError::Disconnected)) => {
+            Outcome::Failed("solver thread exited without an answer".into())
+        }
+        Err(error) => Outcome::Failed(format!("couldn't spawn solver thread: {error}")),
+    }
+}
+
+fn run_timed(day: &Day, part: u8, input: &Arc<str>, options: &Options) -> PartResult {
+    let start = Instant::now();
+    let outcome = match options.isolate {
+        true => isolate::run_part(day, part, input, options)
+            .unwrap_or_else(|error| Outcome::Failed(format!("couldn't run isolated: {error:#}"))),
+        false => match day.solver(part) {
+            Ok(solver) => run_part(solver, input.clone(), options.timeout),
+            Err(error) => Outcome::Failed(format!("{error:#}")),
+        },
+    };
+
+    PartResult {
+        part,
+        outcome,
+        elapsed: start.elapsed(),
+    }
+}
+
+pub fn run_day(day: &Day, options: &Options) -> DayResult {
+    let span = tracing::info_span!("day", year = day.year, day = day.day);
+
+    // only the parts that were asked for and actually exist, part two is often still missing
+    let parts = options
+        .parts()
+        .into_iter()
+        .filter(|part| day.has_part(*part))
+        .collect::<Vec<_>>();
+
+    // without an input none of the parts can run, so mark them all as failed
+    let input = match span.in_scope(|| options.input.load(day.year, day.day)) {
+        Ok(input) => Arc::<str>::from(input),
+        Err(error) => {
+            let parts = parts
+                .into_iter()
+                .map(|part| PartResult {
+                    part,
+                    outcome: Outcome::Failed(format!("{error:#}")),
+                    elapsed: Duration::ZERO,
+                })
+                .collect();
+
+            return DayResult {
+                year: day.year,
+                day: day.day,
+                parts,
+                elapsed: Duration::ZERO,
+                input_hash: None,
+            };
+        }
+    };
+
+    // the parts don't share any state, so let them race each other too
+    let start = Instant::now();
+    let parts = parts
+        .into_par_iter()
+        .map(|part| span.in_scope(|| run_timed(day, part, &input, options)))
+        .collect();
+
+    DayResult {
+        year: day.year,
+        day: day.day,
+        parts,
+        elapsed: start.elapsed(),
+        input_hash: Some(crate::util::hash_input(&input)),
+    }
+}
+
+/// Run every day on a thread pool, the results come back in the same order as `days`.
+/// Panics and errors are caught per part, so the only thing that can fail here is building the pool.
+pub fn run(days: &[Day], options: &Options) -> eyre::Result<Vec<DayResult>> {
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(options.jobs).build()?;
+
+    Ok(pool.install(|| {
+        days.par_iter()
+            .filter(|day| options.includes(day))
+            .map(|day| run_day(day, options))
+            .collect()
+    }))
+}
+
+/// Write the results to stdout in the chosen format, then log every result in order followed by a
+/// summary. Returns true if every part was solved.
+pub fn report(results: &[DayResult], format: output::Format) -> eyre::Result<bool> {
+    output::write(results, format, std::io::stdout().lock())?;
+
+    let mut solved = 0;
+    let mut total = 0;
+
+    for result in results {
+        let prefix = format!("{}/{:0>2}", result.year, result.day);
+
+        for part in result.parts.iter() {
+            let name = part_name(part.part);
+            total += 1;
+
+            if part.outcome.is_solved() {
+                solved += 1;
+                tracing::info!("{prefix} {name}: {} ({:.2?})", part.outcome, part.elapsed);
+            } else {
+                tracing::error!("{prefix} {name}: {} ({:.2?})", part.outcome, part.elapsed);
+            }
+        }
+
+        tracing::info!("{prefix} took {:.2?}", result.elapsed);
+    }
+
+    if solved == total {
+        tracing::info!("solved {solved}/{total} parts");
+    } else {
+        tracing::error!("solved {solved}/{total} parts, {} failed", total - solved);
+    }
+
+    Ok(solved == total)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{run_part, Day, Outcome, Solution};
+
+    const fn solution(part: u8, variant: Option<&'static str>, function: &'static str) -> Solution {
+        Solution {
+            year: 2023,
+            day: 1,
+            part,
+            variant,
+            function,
+            solver: |_| Ok(0u64.into()),
+        }
+    }
+
+    static PART_ONE: Solution = solution(1, None, "day01::part_one");
+    static PART_TWO: Solution = solution(2, None, "day01::part_two");
+    static FAST: Solution = solution(1, Some("fast"), "day01::part_one_fast");
+    static CLASH: Solution = solution(1, None, "day01::part_one_again");
+
+    #[test]
+    fn test_group() {
+        let days = Day::group(vec![&FAST, &PART_TWO, &PART_ONE]);
+        assert_eq!(days.len(), 1);
+
+        let names = days[0].variants(1).iter().map(|s| s.function).collect::<Vec<_>>();
+        assert_eq!(names, vec!["day01::part_one", "day01::part_one_fast"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "day01::part_one and day01::part_one_again are both registered as 2023/01 part 1 (main)")]
+    fn test_group_rejects_duplicates() {
+        Day::group(vec![&PART_ONE, &PART_TWO, &CLASH]);
+    }
+
+    #[test]
+    fn test_run_part_catches_errors_and_panics() {
+        let solved = run_part(|input| Ok(input.len().into()), "abc".into(), None);
+        assert_eq!(solved, Outcome::Solved(3u64.into()));
+
+        let failed = run_part(|_| eyre::bail!("bad input"), "".into(), None);
+        assert_eq!(failed, Outcome::Failed("bad input".into()));
+
+        let panicked = run_part(
+            |input| Ok(input.chars().nth(10).unwrap().to_string().into()),
+            "".into(),
+            None,
+        );
+        assert!(matches!(panicked, Outcome::Panicked(_)));
+    }
+
+    #[test]
+    fn test_run_part_times_out() {
+        let outcome = run_part(
+            |_| loop {
+                super::check_cancelled()?;
+                std::thread::sleep(Duration::from_millis(1));
+            },
+            "".into(),
+            Some(Duration::from_millis(20)),
+        );
+
+        assert_eq!(outcome, Outcome::TimedOut);
+    }
+}
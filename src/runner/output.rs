@@ -0,0 +1,158 @@
+use std::io::Write;
+
+use serde::Serialize;
+
+use super::{DayResult, Outcome};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// human readable, through the logs
+    #[default]
+    Text,
+    /// one JSON object per line, per part
+    Json,
+    /// a header followed by one row per part
+    Csv,
+}
+
+/// A single part of a single day, flattened for scripts and dashboards.
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub year: u64,
+    pub day: u64,
+    pub part: u8,
+    /// one of ok, error, panic or timeout
+    pub status: &'static str,
+    pub answer: Option<String>,
+    pub error: Option<&'a str>,
+    /// includes parsing, see [`super::PartResult::elapsed`]
+    pub elapsed_ns: u64,
+    pub day_elapsed_ns: u64,
+    pub input_hash: Option<&'a str>,
+}
+
+pub fn records(results: &[DayResult]) -> Vec<Record<'_>> {
+    results
+        .iter()
+        .flat_map(|result| {
+            result.parts.iter().map(|part_result| {
+                let (answer, error) = match &part_result.outcome {
+                    Outcome::Solved(answer) => (Some(answer.to_string()), None),
+                    Outcome::Failed(error) | Outcome::Panicked(error) => (None, Some(error.as_str())),
+                    Outcome::TimedOut => (None, None),
+                };
+
+                Record {
+                    year: result.year,
+                    day: result.day,
+                    part: part_result.part,
+                    status: part_result.outcome.status(),
+                    answer,
+                    error,
+                    elapsed_ns: part_result.elapsed.as_nanos() as _,
+                    day_elapsed_ns: result.elapsed.as_nanos() as _,
+                    input_hash: result.input_hash.as_deref(),
+                }
+            })
+        })
+        .collect()
+}
+
+/// Write a record per part in the given format, text is left to the logs so writes nothing.
+pub fn write(results: &[DayResult], format: Format, mut writer: impl Write) -> eyre::Result<()> {
+    match format {
+        Format::Text => {}
+        Format::Json => {
+            for record in records(results) {
+                serde_json::to_writer(&mut writer, &record)?;
+                writeln!(writer)?;
+            }
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(writer);
+            for record in records(results) {
+                writer.serialize(record)?;
+            }
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{records, write, Format};
+    use crate::runner::{DayResult, Outcome, PartResult};
+
+    fn result() -> DayResult {
+        let part = |part, outcome, millis| PartResult {
+            part,
+            outcome,
+            elapsed: Duration::from_millis(millis),
+        };
+
+        DayResult {
+            year: 2023,
+            day: 5,
+            parts: vec![
+                part(1, Outcome::Solved(35u64.into()), 1),
+                part(2, Outcome::Failed("no seeds, \"none\"".into()), 2),
+                part(1, Outcome::Panicked("oops".into()), 3),
+                part(2, Outcome::TimedOut, 4),
+            ],
+            elapsed: Duration::from_millis(5),
+            input_hash: Some("abc".into()),
+        }
+    }
+
+    fn render(format: Format) -> String {
+        let mut output = Vec::new();
+        write(&[result()], format, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_records() {
+        let results = [result()];
+        let records = records(&results);
+
+        let statuses = records.iter().map(|record| record.status).collect::<Vec<_>>();
+        assert_eq!(statuses, vec!["ok", "error", "panic", "timeout"]);
+        assert_eq!(records[0].answer.as_deref(), Some("35"));
+        assert_eq!(records[2].error, Some("oops"));
+        assert_eq!((records[3].answer.as_deref(), records[3].error), (None, None));
+        assert!(records.iter().all(|record| record.input_hash == Some("abc")));
+    }
+
+    #[test]
+    fn test_json_and_csv() {
+        let json = render(Format::Json);
+        let lines = json.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            r#"{"year":2023,"day":5,"part":1,"status":"ok","answer":"35","error":null,"elapsed_ns":1000000,"day_elapsed_ns":5000000,"input_hash":"abc"}"#
+        );
+        assert_eq!(
+            lines[3],
+            r#"{"year":2023,"day":5,"part":2,"status":"timeout","answer":null,"error":null,"elapsed_ns":4000000,"day_elapsed_ns":5000000,"input_hash":"abc"}"#
+        );
+
+        let csv = render(Format::Csv);
+        assert_eq!(
+            csv.lines().collect::<Vec<_>>(),
+            vec![
+                "year,day,part,status,answer,error,elapsed_ns,day_elapsed_ns,input_hash",
+                "2023,5,1,ok,35,,1000000,5000000,abc",
+                r#"2023,5,2,error,,"no seeds, ""none""",2000000,5000000,abc"#,
+                "2023,5,1,panic,,oops,3000000,5000000,abc",
+                "2023,5,2,timeout,,,4000000,5000000,abc",
+            ]
+        );
+
+        assert!(render(Format::Text).is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+use std::path::Path;
+
+use crate::examples;
+
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+const BENCH_TEMPLATE: &str = include_str!("../templates/bench.rs.tmpl");
+const ANSWERS_HEADER: &str = "# example part answer\n";
+
+fn render(template: &str, year: u64, day: u64) -> String {
+    template
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.to_string())
+        .replace("{{dd}}", &format!("{day:0>2}"))
+}
+
+fn write_new(path: &Path, contents: &str) -> eyre::Result<()> {
+    eyre::ensure!(!path.exists(), "{} already exists", path.display());
+    std::fs::write(path, contents)?;
+    tracing::info!("created {}", path.display());
+
+    Ok(())
+}
+
+/// Add `pub mod {module};` to a list of sibling modules sharing `prefix`, keeping them sorted. If there
+/// aren't any siblings yet it goes at the end.
+fn insert_module(source: &str, prefix: &str, module: &str) -> String {
+    let declaration = format!("pub mod {module};");
+    let prefix = format!("pub mod {prefix}");
+    let mut lines = source.lines().map(String::from).collect::<Vec<_>>();
+
+    // slot the new one in after the last sibling that sorts before it
+    let position = lines
+        .iter()
+        .rposition(|line| line.starts_with(&prefix) && *line < declaration)
+        .map(|index| index + 1)
+        .or_else(|| lines.iter().position(|line| line.starts_with(&prefix)))
+        .unwrap_or(lines.len());
+    lines.insert(position, declaration);
+
+    lines.join("\n") + "\n"
+}
+
+/// Stamp out a new day: the solution module, its `pub mod` line, a criterion bench and an empty `answers.txt`
+/// for its examples, plus the year's module and its line in `lib.rs` if this is its first day. Paths are relative to the repository root,
+/// which is where cargo runs the binary from.
+pub fn new_day(year: u64, day: u64) -> eyre::Result<()> {
+    eyre::ensure!((1..=25).contains(&day), "{day} is not a valid AoC day.");
+    eyre::ensure!(Path::new("Cargo.toml").is_file(), "run this from the repository root");
+
+    let lib_path = Path::new("src").join("lib.rs");
+    let year_dir = Path::new("src").join(format!("year{year}"));
+    let mod_path = year_dir.join("mod.rs");
+    let day_path = year_dir.join(format!("day{day:0>2}.rs"));
+    let bench = format!("y{year}d{day:0>2}");
+    let bench_path = Path::new("benches").join(format!("{bench}.rs"));
+    let examples_dir = examples::day_dir(year, day);
+    let answers_path = examples_dir.join("answers.txt");
+
+    // build and check everything up front, so a failure doesn't leave things half done
+    let (lib_rs, mod_rs) = match mod_path.is_file() {
+        true => (None, std::fs::read_to_string(&mod_path)?),
+        false => {
+            let lib_rs = insert_module(&std::fs::read_to_string(&lib_path)?, "year", &format!("year{year}"));
+            (Some(lib_rs), String::new())
+        }
+    };
+    let mod_rs = insert_module(&mod_rs, "day", &format!("day{day:0>2}"));
+    let cargo_toml = std::fs::read_to_string("Cargo.toml")?;
+    eyre::ensure!(
+        !cargo_toml.contains(&format!("name    = \"{bench}\"")),
+        "{bench} is already in Cargo.toml"
+    );
+    for path in [&day_path, &bench_path, &answers_path] {
+        eyre::ensure!(!path.exists(), "{} already exists", path.display());
+    }
+
+    // a new year's days register themselves, so all it needs is its module
+    if let Some(lib_rs) = lib_rs {
+        std::fs::create_dir_all(&year_dir)?;
+        std::fs::write(&lib_path, lib_rs)?;
+        tracing::info!("added year{year} to {}", lib_path.display());
+    }
+
+    write_new(&day_path, &render(DAY_TEMPLATE, year, day))?;
+    write_new(&bench_path, &render(BENCH_TEMPLATE, year, day))?;
+    std::fs::create_dir_all(&examples_dir)?;
+    write_new(&answers_path, ANSWERS_HEADER)?;
+
+    std::fs::write(&mod_path, mod_rs)?;
+    tracing::info!("added day{day:0>2} to {}", mod_path.display());
+
+    let cargo_toml = format!(
+        "{}\n\n[[bench]]\nname    = \"{bench}\"\nharness = false\n",
+        cargo_toml.trim_end()
+    );
+    std::fs::write("Cargo.toml", cargo_toml)?;
+    tracing::info!("added {bench} to Cargo.toml");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_insert_module() {
+        let mod_rs = "pub mod day01;\npub mod day03;\n";
+        let result = super::insert_module(mod_rs, "day", "day02");
+        assert_eq!(result, "pub mod day01;\npub mod day02;\npub mod day03;\n");
+
+        let result = super::insert_module("", "day", "day01");
+        assert_eq!(result, "pub mod day01;\n");
+
+        let lib_rs = "pub mod watch;\n\npub mod year2023;\n\npub fn days() {}\n";
+        let result = super::insert_module(lib_rs, "year", "year2024");
+        assert_eq!(
+            result,
+            "pub mod watch;\n\npub mod year2023;\npub mod year2024;\n\npub fn days() {}\n"
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+/// A point or vector on a plane where `y` grows downwards, matching how puzzle grids are read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn scale(self, factor: T) -> Self
+    where
+        T: Mul<Output = T> + Copy,
+    {
+        Self::new(self.x * factor, self.y * factor)
+    }
+
+    /// the number of orthogonal steps between two points
+    pub fn manhattan(self, other: Self) -> T
+    where
+        T: Sub<Output = T> + Add<Output = T> + Ord,
+    {
+        // works for unsigned types too, where a plain `abs` doesn't exist
+        let distance = |a: T, b: T| if a > b { a - b } else { b - a };
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    /// a quarter turn clockwise about the origin, so north becomes east
+    pub fn rotate_clockwise(self) -> Self
+    where
+        T: Neg<Output = T>,
+    {
+        Self::new(-self.y, self.x)
+    }
+
+    /// a quarter turn anticlockwise about the origin, so north becomes west
+    pub fn rotate_anticlockwise(self) -> Self
+    where
+        T: Neg<Output = T>,
+    {
+        Self::new(self.y, -self.x)
+    }
+
+    /// one step in a direction
+    pub fn step(self, direction: Direction) -> Self
+    where
+        T: Add<Output = T> + From<i8>,
+    {
+        self + direction.offset()
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+/// The eight compass directions, in clockwise order starting from north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /// just the orthogonal directions, no diagonals
+    pub const CARDINAL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// turn by a number of eighths, clockwise when positive
+    fn turn(self, eighths: i8) -> Self {
+        Self::ALL[(self as i8 + eighths).rem_euclid(8) as usize]
+    }
+
+    /// a quarter turn clockwise
+    pub fn turn_right(self) -> Self {
+        self.turn(2)
+    }
+
+    /// a quarter turn anticlockwise
+    pub fn turn_left(self) -> Self {
+        self.turn(-2)
+    }
+
+    pub fn turn_around(self) -> Self {
+        self.turn(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !Self::CARDINAL.contains(&self)
+    }
+
+    /// the vector a single step in this direction moves by
+    pub fn offset<T: From<i8>>(self) -> Point<T> {
+        let (x, y) = match self {
+            Self::North => (0, -1),
+            Self::NorthEast => (1, -1),
+            Self::East => (1, 0),
+            Self::SouthEast => (1, 1),
+            Self::South => (0, 1),
+            Self::SouthWest => (-1, 1),
+            Self::West => (-1, 0),
+            Self::NorthWest => (-1, -1),
+        };
+
+        Point::new(T::from(x), T::from(y))
+    }
+}
+
+/// Arrows `^>v<`, letters `UDLR` and compass points `N`, `NE` ... `NW`.
+impl FromStr for Direction {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        Ok(match s {
+            "^" | "U" | "N" => Self::North,
+            "NE" => Self::NorthEast,
+            ">" | "R" | "E" => Self::East,
+            "SE" => Self::SouthEast,
+            "v" | "D" | "S" => Self::South,
+            "SW" => Self::SouthWest,
+            "<" | "L" | "W" => Self::West,
+            "NW" => Self::NorthWest,
+            _ => eyre::bail!("`{s}` isn't a direction"),
+        })
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = eyre::Error;
+
+    fn try_from(c: char) -> eyre::Result<Self> {
+        c.encode_utf8(&mut [0; 4]).parse()
+    }
+}
+
+/// each edge of a polygon, the last vertex joins back up with the first
+fn edges(vertices: &[Point<i64>]) -> impl Iterator<Item = (Point<i64>, Point<i64>)> + '_ {
+    vertices.iter().copied().zip(vertices.iter().copied().cycle().skip(1))
+}
+
+/// twice the area, which keeps it an exact integer for vertices on a lattice
+fn twice_area(vertices: &[Point<i64>]) -> i64 {
+    edges(vertices).map(|(a, b)| a.x * b.y - b.x * a.y).sum::<i64>().abs()
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    match b {
+        0 => a.abs(),
+        b => gcd(b, a % b),
+    }
+}
+
+/// The area enclosed by a simple polygon, using the shoelace formula. The vertices can go either way
+/// round, and a lattice polygon's area is always a whole or half number.
+pub fn shoelace_area(vertices: &[Point<i64>]) -> f64 {
+    twice_area(vertices) as f64 / 2.0
+}
+
+/// The number of lattice points on a polygon's edges, the `b` in Pick's theorem. With only horizontal and
+/// vertical edges, which is all a grid walk produces, this is also the length of the perimeter.
+pub fn boundary_points(vertices: &[Point<i64>]) -> i64 {
+    edges(vertices).map(|(a, b)| gcd(b.x - a.x, b.y - a.y)).sum()
+}
+
+/// The number of lattice points strictly inside a polygon, from Pick's theorem `A = i + b/2 - 1`.
+pub fn interior_points(vertices: &[Point<i64>]) -> i64 {
+    (twice_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Point};
+
+    #[test]
+    fn test_point_arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 4);
+
+        assert_eq!(a + b, Point::new(2, 2));
+        assert_eq!(a - b, Point::new(4, -6));
+        assert_eq!(a.scale(2), Point::new(6, -4));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(Point::new(2u64, 7).manhattan(Point::new(5, 1)), 9);
+        assert_eq!(a.rotate_clockwise().rotate_anticlockwise(), a);
+    }
+
+    #[test]
+    fn test_directions() {
+        let north = Point::new(0, 0).step(Direction::North);
+        assert_eq!(north, Point::new(0i64, -1));
+        assert_eq!(north.rotate_clockwise(), Direction::East.offset());
+
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::SouthWest.turn_around(), Direction::NorthEast);
+        assert_eq!(Direction::NorthWest.turn_right(), Direction::NorthEast);
+
+        let arrows = "^>v<"
+            .chars()
+            .map(Direction::try_from)
+            .collect::<eyre::Result<Vec<_>>>();
+        let letters = "URDL"
+            .chars()
+            .map(Direction::try_from)
+            .collect::<eyre::Result<Vec<_>>>();
+        assert_eq!(arrows.unwrap(), Direction::CARDINAL);
+        assert_eq!(letters.unwrap(), Direction::CARDINAL);
+        assert!("x".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn test_polygons() {
+        // a 4x3 rectangle
+        let rectangle = [(0, 0), (4, 0), (4, 3), (0, 3)].map(Point::from);
+        assert_eq!(super::shoelace_area(&rectangle), 12.0);
+        assert_eq!(super::boundary_points(&rectangle), 14);
+        assert_eq!(super::interior_points(&rectangle), 3 * 2);
+
+        // a triangle going the other way round, with a half area
+        let triangle = [(0, 0), (1, 2), (3, 0)].map(Point::from);
+        assert_eq!(super::shoelace_area(&triangle), 3.0);
+        assert_eq!(super::boundary_points(&triangle), 6);
+        assert_eq!(super::interior_points(&triangle), 1);
+
+        let sliver = [(0, 0), (1, 0), (0, 1)].map(Point::from);
+        assert_eq!(super::shoelace_area(&sliver), 0.5);
+    }
+}
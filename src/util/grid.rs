@@ -0,0 +1,343 @@
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+/// A cell's `(x, y)` position, `x` runs along a row and `y` down the columns.
+pub type Position = (usize, usize);
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const SURROUNDING: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)];
+
+/// A rectangular grid of cells stored row by row, the bread and butter of 2D puzzles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// build a grid from its cells in row order
+    pub fn from_cells(width: usize, cells: Vec<T>) -> eyre::Result<Self> {
+        // a zero width only fits zero cells
+        eyre::ensure!(
+            cells.len().is_multiple_of(width),
+            "{} cells don't fill rows of {width}",
+            cells.len()
+        );
+
+        Ok(Self {
+            width,
+            height: cells.len().checked_div(width).unwrap_or(0),
+            cells,
+        })
+    }
+
+    /// Parse a grid a character at a time, every line is a row and they must all be the same length.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> eyre::Result<T>) -> eyre::Result<Self> {
+        let mut width = None;
+        let mut cells = Vec::new();
+
+        for (y, line) in input.lines().enumerate() {
+            let before = cells.len();
+            for c in line.chars() {
+                cells.push(cell(c)?);
+            }
+
+            let length = cells.len() - before;
+            match width {
+                None => width = Some(length),
+                Some(width) => eyre::ensure!(width == length, "row {y} is {length} long, expected {width}"),
+            }
+        }
+
+        Self::from_cells(width.unwrap_or(0), cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.1 * self.width + position.0])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        match self.contains(position) {
+            true => Some(&mut self.cells[position.1 * self.width + position.0]),
+            false => None,
+        }
+    }
+
+    /// step from a position by `(dx, dy)`, `None` if that leaves the grid
+    pub fn offset(&self, (x, y): Position, (dx, dy): (isize, isize)) -> Option<Position> {
+        let position = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// the up to 4 positions directly above, right, below and left of a position
+    pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |delta| self.offset(position, delta))
+    }
+
+    /// the up to 8 positions surrounding a position, diagonals included
+    pub fn surrounding(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        SURROUNDING
+            .into_iter()
+            .filter_map(move |delta| self.offset(position, delta))
+    }
+
+    /// every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// every cell alongside its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on a zero width, an empty grid has no rows anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// build a new grid with the given size, where `source` says which of our positions each cell copies
+    fn rearrange(&self, width: usize, height: usize, source: impl Fn(Position) -> Position) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|position| self[source(position)].clone())
+            .collect();
+
+        Self { width, height, cells }
+    }
+
+    /// swap rows and columns, mirroring along the top left to bottom right diagonal
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearrange(self.height, self.width, |(x, y)| (y, x))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearrange(self.height, self.width, |(x, y)| (y, self.height - 1 - x))
+    }
+
+    pub fn rotate_anticlockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearrange(self.height, self.width, |(x, y)| (self.width - 1 - y, x))
+    }
+
+    /// Flood fill from `start` through orthogonal neighbours for as long as `include` allows, returning
+    /// the positions reached in the order they were found. Empty if `start` itself isn't included.
+    pub fn region(&self, start: Position, mut include: impl FnMut(Position, &T) -> bool) -> Vec<Position> {
+        let mut seen = Grid::new(self.width, self.height, false);
+        let mut queue = VecDeque::new();
+        let mut region = Vec::new();
+
+        if self.get(start).is_some_and(|cell| include(start, cell)) {
+            seen[start] = true;
+            queue.push_back(start);
+        }
+
+        while let Some(position) = queue.pop_front() {
+            region.push(position);
+
+            for next in self.neighbours(position) {
+                if !seen[next] && include(next, &self[next]) {
+                    seen[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        region
+    }
+
+    /// split the grid into orthogonally connected regions of equal cells, in the order their first
+    /// cell appears
+    pub fn regions(&self) -> Vec<Vec<Position>>
+    where
+        T: PartialEq,
+    {
+        let mut claimed = Grid::new(self.width, self.height, false);
+        let mut regions = Vec::new();
+
+        for position in self.positions() {
+            if claimed[position] {
+                continue;
+            }
+
+            let value = &self[position];
+            let region = self.region(position, |_, cell| cell == value);
+            for member in region.iter() {
+                claimed[*member] = true;
+            }
+
+            regions.push(region);
+        }
+
+        regions
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get(position)
+            .unwrap_or_else(|| panic!("{position:?} is outside a {width}x{height} grid"))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position:?} is outside a {width}x{height} grid"))
+    }
+}
+
+/// Writes each cell back out, a row per line, so a `Grid<char>` displays as the text it came from.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+
+    const EXAMPLE: &str = "ab.\n.bb\ncc.";
+
+    fn example() -> Grid<char> {
+        Grid::parse(EXAMPLE, Ok).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[(1, 0)], 'b');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), EXAMPLE);
+
+        assert!(Grid::parse("ab\nc", Ok).is_err());
+        assert!(Grid::parse("a?", |c| match c {
+            '?' => eyre::bail!("unknown cell"),
+            c => Ok(c),
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = example();
+
+        let corner = grid.neighbours((0, 0)).collect::<Vec<_>>();
+        assert_eq!(corner, vec![(1, 0), (0, 1)]);
+
+        assert_eq!(grid.neighbours((1, 1)).count(), 4);
+        assert_eq!(grid.surrounding((1, 1)).count(), 8);
+        assert_eq!(grid.surrounding((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::parse("abc\ndef", Ok).unwrap();
+
+        let rows = grid
+            .rows()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec!["abc", "def"]);
+
+        let columns = grid
+            .columns()
+            .map(|column| column.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn test_rotation_and_transposition() {
+        let grid = Grid::parse("abc\ndef", Ok).unwrap();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_anticlockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_anticlockwise(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn test_regions() {
+        let grid = example();
+        let regions = grid.regions();
+
+        // a, the b's, the top right dot, the middle left dot, the c's, bottom right dot
+        assert_eq!(regions.len(), 6);
+        assert_eq!(regions[1], vec![(1, 0), (1, 1), (2, 1)]);
+        assert_eq!(regions.iter().map(Vec::len).sum::<usize>(), 9);
+
+        assert!(grid.region((0, 0), |_, cell| *cell == '.').is_empty());
+    }
+}
@@ -0,0 +1,230 @@
+use std::ops::Range;
+
+/// A set of integers stored as sorted, disjoint, half open ranges. Ranges that overlap or touch are
+/// coalesced as they go in, so there's only ever one way to hold the same set. This lets range heavy
+/// puzzles work on spans instead of iterating billions of values.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<Range<i64>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the disjoint ranges making up the set, in order
+    pub fn ranges(&self) -> &[Range<i64>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// how many values are in the set
+    pub fn size(&self) -> i64 {
+        self.ranges.iter().map(|range| range.end - range.start).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.ranges.last().map(|range| range.end - 1)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        // the last range starting at or before the value is the only one that can hold it
+        let index = self.ranges.partition_point(|range| range.start <= value);
+        index > 0 && self.ranges[index - 1].contains(&value)
+    }
+
+    pub fn insert(&mut self, range: Range<i64>) {
+        *self = self.ranges.iter().cloned().chain([range]).collect();
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.ranges.iter().chain(other.ranges.iter()).cloned().collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (self.ranges.iter().peekable(), other.ranges.iter().peekable());
+
+        // walk both lists together, always moving past whichever range ends first
+        while let (Some(left), Some(right)) = (a.peek(), b.peek()) {
+            let overlap = left.start.max(right.start)..left.end.min(right.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+
+            match left.end <= right.end {
+                true => a.next(),
+                false => b.next(),
+            };
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut others = other.ranges.iter().peekable();
+
+        for range in self.ranges.iter() {
+            let mut start = range.start;
+
+            // skip anything that finishes before this range starts
+            while others.next_if(|other| other.end <= start).is_some() {}
+
+            // cut out every range that starts inside this one, the last may carry on into the next
+            while let Some(other) = others.peek().filter(|other| other.start < range.end) {
+                if other.start > start {
+                    ranges.push(start..other.start);
+                }
+                start = start.max(other.end);
+
+                if other.end > range.end {
+                    break;
+                }
+                others.next();
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// move every value by `delta`
+    pub fn shift(&self, delta: i64) -> Self {
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|range| range.start + delta..range.end + delta)
+            .collect();
+
+        Self { ranges }
+    }
+
+    /// Cut the ranges wherever one of `points` falls inside them, so each piece starts at a point or
+    /// an original start. The pieces aren't coalesced, as that would just join them back up.
+    pub fn split_at(&self, points: &[i64]) -> Vec<Range<i64>> {
+        let mut points = points.to_vec();
+        points.sort_unstable();
+        points.dedup();
+
+        let mut pieces = Vec::new();
+        for range in self.ranges.iter() {
+            let mut start = range.start;
+
+            let first = points.partition_point(|point| *point <= range.start);
+            for point in points[first..].iter().take_while(|point| **point < range.end) {
+                pieces.push(start..*point);
+                start = *point;
+            }
+
+            pieces.push(start..range.end);
+        }
+
+        pieces
+    }
+}
+
+/// Collects any ranges at all, dropping empty ones and coalescing the rest.
+impl FromIterator<Range<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        let mut ranges = iter.into_iter().filter(|range| !range.is_empty()).collect::<Vec<_>>();
+        ranges.sort_unstable_by_key(|range| range.start);
+
+        let mut coalesced = Vec::<Range<i64>>::with_capacity(ranges.len());
+        for range in ranges {
+            match coalesced.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => coalesced.push(range),
+            }
+        }
+
+        Self { ranges: coalesced }
+    }
+}
+
+impl From<Range<i64>> for IntervalSet {
+    fn from(range: Range<i64>) -> Self {
+        [range].into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, ops::Range};
+
+    use proptest::prelude::*;
+
+    use super::IntervalSet;
+
+    /// small ranges on a small domain, so they overlap and touch a lot
+    fn ranges() -> impl Strategy<Value = Vec<Range<i64>>> {
+        prop::collection::vec(
+            (-40i64..40, 0i64..15).prop_map(|(start, length)| start..start + length),
+            0..6,
+        )
+    }
+
+    fn values(set: &IntervalSet) -> BTreeSet<i64> {
+        set.ranges().iter().flat_map(Clone::clone).collect()
+    }
+
+    fn is_canonical(set: &IntervalSet) -> bool {
+        set.ranges().iter().all(|range| !range.is_empty())
+            && set.ranges().windows(2).all(|pair| pair[0].end < pair[1].start)
+    }
+
+    #[test]
+    fn test_coalescing() {
+        let set = [5..8, 0..2, 2..3, 7..10, 12..12].into_iter().collect::<IntervalSet>();
+        assert_eq!(set.ranges(), &[0..3, 5..10]);
+        assert_eq!(set.size(), 8);
+        assert_eq!((set.min(), set.max()), (Some(0), Some(9)));
+        assert!(set.contains(9) && !set.contains(10) && !set.contains(4));
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_a_plain_set(a in ranges(), b in ranges(), delta in -50i64..50) {
+            let (a, b) = (a.into_iter().collect::<IntervalSet>(), b.into_iter().collect::<IntervalSet>());
+            let (a_values, b_values) = (values(&a), values(&b));
+
+            for result in [a.union(&b), a.intersection(&b), a.difference(&b), a.shift(delta)] {
+                prop_assert!(is_canonical(&result), "{result:?}");
+            }
+
+            prop_assert_eq!(values(&a.union(&b)), &a_values | &b_values);
+            prop_assert_eq!(values(&a.intersection(&b)), &a_values & &b_values);
+            prop_assert_eq!(values(&a.difference(&b)), &a_values - &b_values);
+            prop_assert_eq!(values(&a.shift(delta)), a_values.iter().map(|v| v + delta).collect());
+            prop_assert_eq!(a.size(), a_values.len() as i64);
+
+            for value in -60..60 {
+                prop_assert_eq!(a.contains(value), a_values.contains(&value));
+            }
+        }
+
+        #[test]
+        fn test_split_at(a in ranges(), points in prop::collection::vec(-60i64..60, 0..8)) {
+            let set = a.into_iter().collect::<IntervalSet>();
+            let pieces = set.split_at(&points);
+
+            // the same values, and no piece has a point strictly inside it
+            prop_assert_eq!(pieces.iter().cloned().collect::<IntervalSet>(), set.clone());
+            prop_assert!(pieces.iter().all(|piece| !piece.is_empty()));
+            prop_assert!(pieces
+                .iter()
+                .all(|piece| points.iter().all(|point| *point <= piece.start || *point >= piece.end)));
+        }
+    }
+}
@@ -1,8 +1,27 @@
-use std::sync::Arc;
+pub mod geom;
+pub mod grid;
+pub mod intervals;
+
+use std::{path::PathBuf, sync::Arc};
 
 use eyre::Context;
 use reqwest::{cookie::Jar, Url};
+use sha2::{Digest, Sha256};
+
+/// hex encoded sha256 of an input, handy for spotting which input produced an answer
+pub fn hash_input(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
 
+/// where a day's input is cached, under `AOC_INPUT_DIR` or `input/`, it may not exist yet
+pub fn input_path(year: u64, day: u64) -> PathBuf {
+    let base_dir = std::env::var("AOC_INPUT_DIR").unwrap_or_else(|_| "input".into());
+    PathBuf::from(base_dir)
+        .join(year.to_string())
+        .join(format!("{day:0>2}.txt"))
+}
+
+#[tracing::instrument]
 pub fn get_input(year: u64, day: u64) -> eyre::Result<String> {
     // grab the base url or use a default
     let base_url = std::env::var("AOC_URL").unwrap_or_else(|_| "https://adventofcode.com".into());
@@ -13,14 +32,11 @@ pub fn get_input(year: u64, day: u64) -> eyre::Result<String> {
     );
 
     // validate year/day
-    eyre::ensure!((2015..=2023).contains(&year), "{year} is not a valid AoC year.");
+    eyre::ensure!(year >= 2015, "{year} is not a valid AoC year.");
     eyre::ensure!((1..=25).contains(&day), "{year} is not a valid AoC day.");
 
     // check for a cached file
-    let base_dir_raw = std::env::var("AOC_INPUT_DIR").unwrap_or_else(|_| "input".into());
-    let base_dir = base_dir_raw.parse::<std::path::PathBuf>()?;
-
-    let file_path = base_dir.join(year.to_string()).join(format!("{day:0>2}.txt"));
+    let file_path = input_path(year, day);
 
     // cache hit! return the file
     if file_path.is_file() {
@@ -0,0 +1,133 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    process::{Command, Stdio},
+    time::{Duration, SystemTime},
+};
+
+use crate::{examples, util};
+
+/// how often to check the watched files for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The day's source file, its cached input and its example fixtures. The fixture directory itself is
+/// included too, so adding or removing an example counts as a change.
+fn watched_paths(year: u64, day: u64) -> Vec<PathBuf> {
+    let examples_dir = examples::day_dir(year, day);
+
+    let mut paths = vec![
+        PathBuf::from(format!("src/year{year}/day{day:0>2}.rs")),
+        util::input_path(year, day),
+    ];
+
+    if let Ok(entries) = std::fs::read_dir(&examples_dir) {
+        let mut fixtures = entries.filter_map(|entry| Some(entry.ok()?.path())).collect::<Vec<_>>();
+        fixtures.sort();
+        paths.extend(fixtures);
+    }
+
+    paths.push(examples_dir);
+    paths
+}
+
+/// last modified time of each path, files that don't exist yet are `None`
+fn modified(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| std::fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .collect()
+}
+
+/// run cargo with its output going straight to the terminal, so errors keep their colours and layout
+fn cargo(args: &[&str]) -> eyre::Result<bool> {
+    Ok(Command::new("cargo")
+        .args(args)
+        .arg("--color=always")
+        .status()?
+        .success())
+}
+
+/// solve the day with a fresh build, returning a printable result for each part
+fn solve(year: u64, day: u64, part: Option<u8>) -> eyre::Result<HashMap<u8, String>> {
+    let mut command = Command::new("cargo");
+    command
+        .args(["run", "--quiet", "--", "--format", "json"])
+        .args(["--year", &year.to_string(), "--day", &day.to_string()])
+        .env("RUST_LOG", "warn")
+        .stderr(Stdio::inherit());
+
+    if let Some(part) = part {
+        command.args(["--part", &part.to_string()]);
+    }
+
+    let output = command.output()?;
+    let mut results = HashMap::new();
+
+    for line in String::from_utf8(output.stdout)?.lines() {
+        let record = serde_json::from_str::<serde_json::Value>(line)?;
+        let part = record["part"]
+            .as_u64()
+            .ok_or_else(|| eyre::eyre!("record without a part"))? as u8;
+
+        let result = match (record["answer"].as_str(), record["error"].as_str()) {
+            (Some(answer), _) => answer.to_string(),
+            (None, Some(error)) => format!("{}: {error}", record["status"]),
+            (None, None) => record["status"].to_string(),
+        };
+
+        results.insert(part, result);
+    }
+
+    Ok(results)
+}
+
+/// Rebuild, test and solve a day every time its source or input changes, comparing each answer with
+/// the one from the previous run.
+pub fn watch(year: u64, day: u64, part: Option<u8>) -> eyre::Result<()> {
+    let mut last_modified = None;
+    let mut answers = HashMap::new();
+
+    tracing::info!("watching {:?}", watched_paths(year, day));
+
+    loop {
+        // look for the paths again each time round, examples come and go
+        let paths = watched_paths(year, day);
+        let current = paths.iter().cloned().zip(modified(&paths)).collect::<Vec<_>>();
+        if last_modified.as_ref() == Some(&current) {
+            std::thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+        last_modified = Some(current);
+
+        tracing::info!("rebuilding {year}/{day:0>2}");
+        if !cargo(&["build", "--quiet"])? {
+            tracing::error!("build failed, waiting for changes");
+            continue;
+        }
+
+        if !cargo(&["test", "--quiet", "--lib", &format!("year{year}::day{day:0>2}")])? {
+            tracing::warn!("tests failed");
+        }
+
+        if !cargo(&["test", "--quiet", "--test", "examples"])? {
+            tracing::warn!("examples failed");
+        }
+
+        let mut results = match solve(year, day, part) {
+            Ok(results) => results.into_iter().collect::<Vec<_>>(),
+            Err(error) => {
+                tracing::error!("couldn't solve: {error:#}, waiting for changes");
+                continue;
+            }
+        };
+        results.sort();
+
+        for (part, answer) in results {
+            match answers.insert(part, answer.clone()) {
+                Some(previous) if previous == answer => tracing::info!("Part {part}: {answer} (unchanged)"),
+                Some(previous) => tracing::info!("Part {part}: {answer} (was {previous})"),
+                None => tracing::info!("Part {part}: {answer}"),
+            }
+        }
+    }
+}
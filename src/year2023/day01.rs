@@ -1,5 +1,7 @@
 use eyre::ContextCompat;
 
+use crate::aoc;
+
 const NUMBERS: [(&str, u64); 9] = [
     ("one", 1),
     ("two", 2),
@@ -60,23 +62,18 @@ fn solve(input: &str, line_to_numbers: impl Sync + Fn(&str) -> eyre::Result<Vec<
         .sum())
 }
 
+#[aoc(year = 2023, day = 1, part = 1)]
+#[tracing::instrument(skip_all)]
 pub fn part_one(input: &str) -> eyre::Result<u64> {
     solve(input, parse_numbers)
 }
 
+#[aoc(year = 2023, day = 1, part = 2)]
+#[tracing::instrument(skip_all)]
 pub fn part_two(input: &str) -> eyre::Result<u64> {
     solve(input, parse_numbers_and_words)
 }
 
-pub fn main() -> eyre::Result<()> {
-    let input = crate::util::get_input(2023, 1)?;
-
-    tracing::info!("Part One: {}", part_one(&input)?);
-    tracing::info!("Part Two: {}", part_two(&input)?);
-
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     #[test]
@@ -2,6 +2,8 @@ use std::str::FromStr;
 
 use eyre::ContextCompat;
 
+use crate::{aoc, aoc_parse};
+
 #[derive(Debug, Default)]
 pub struct Set {
     pub red: u64,
@@ -74,6 +76,8 @@ impl Game {
     }
 }
 
+#[aoc(year = 2023, day = 2, part = 1)]
+#[tracing::instrument(skip_all)]
 pub fn part_one(games: &[Game]) -> eyre::Result<u64> {
     Ok(games
         .iter()
@@ -86,16 +90,14 @@ pub fn part_one(games: &[Game]) -> eyre::Result<u64> {
         .sum())
 }
 
+#[aoc(year = 2023, day = 2, part = 2)]
+#[tracing::instrument(skip_all)]
 pub fn part_two(games: &[Game]) -> eyre::Result<u64> {
     Ok(games.iter().map(|game| game.max().product()).sum())
 }
 
-pub fn main() -> eyre::Result<()> {
-    let input = crate::util::get_input(2023, 2)?;
-    let games = input.lines().map(Game::from_str).collect::<eyre::Result<Vec<_>>>()?;
-
-    tracing::info!("Part One: {}", part_one(&games)?);
-    tracing::info!("Part Two: {}", part_two(&games)?);
-
-    Ok(())
+#[aoc_parse(year = 2023, day = 2)]
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> eyre::Result<Vec<Game>> {
+    input.lines().map(Game::from_str).collect()
 }
@@ -1,5 +1,13 @@
 use std::{ops::RangeInclusive, str::FromStr};
 
+use itertools::Itertools;
+
+use crate::{
+    aoc, aoc_parse,
+    repl::{self, Inspect, Model},
+    util::geom::{Direction, Point},
+};
+
 #[derive(Debug, Clone)]
 pub struct Number {
     pub value: u64,
@@ -8,14 +16,21 @@ pub struct Number {
 }
 
 impl Number {
-    pub fn surroundings(&self) -> Vec<(isize, isize)> {
+    /// every point next to one of the digits, diagonals included
+    pub fn surroundings(&self) -> Vec<Point<isize>> {
+        let (start, end) = (Point::new(*self.x.start(), self.y), Point::new(*self.x.end(), self.y));
         let mut points = Vec::new();
 
-        // top/bottom/left/right
-        points.extend((self.x.start() - 1..=self.x.end() + 1).map(|x| (x, self.y - 1)));
-        points.extend((self.x.start() - 1..=self.x.end() + 1).map(|x| (x, self.y + 1)));
-        points.push((self.x.start() - 1, self.y));
-        points.push((self.x.end() + 1, self.y));
+        // the rows above and below reach one past each end, then the points either side
+        for direction in [Direction::North, Direction::South] {
+            let (left, right) = (
+                start.step(direction).step(Direction::West),
+                end.step(direction).step(Direction::East),
+            );
+            points.extend((left.x..=right.x).map(|x| Point::new(x, left.y)));
+        }
+        points.push(start.step(Direction::West));
+        points.push(end.step(Direction::East));
 
         points
     }
@@ -32,7 +47,7 @@ impl Symbol {
     pub fn neighbours(&self, numbers: &[Number]) -> Vec<Number> {
         numbers
             .iter()
-            .filter(|number| number.surroundings().iter().any(|(x, y)| x == &self.x && y == &self.y))
+            .filter(|number| number.surroundings().contains(&Point::new(self.x, self.y)))
             .cloned()
             .collect()
     }
@@ -60,9 +75,11 @@ fn build_number(digits: &mut Vec<(isize, char)>, numbers: &mut Vec<Number>, y: i
     Ok(())
 }
 
+#[aoc_parse(year = 2023, day = 3)]
 impl FromStr for Schematic {
     type Err = eyre::Error;
 
+    #[tracing::instrument(name = "parse", skip_all)]
     fn from_str(input: &str) -> eyre::Result<Self> {
         let mut digits = Vec::new();
         let mut numbers = Vec::new();
@@ -95,6 +112,62 @@ impl FromStr for Schematic {
     }
 }
 
+/// which numbers touch the symbol at the x, y given in `args`
+fn describe_neighbours(schematic: &Schematic, args: &[&str]) -> eyre::Result<String> {
+    let x: isize = repl::arg(args, 0, "x")?;
+    let y: isize = repl::arg(args, 1, "y")?;
+    let symbol = schematic
+        .symbols
+        .iter()
+        .find(|symbol| symbol.x == x && symbol.y == y)
+        .ok_or_else(|| eyre::eyre!("no symbol at ({x}, {y})"))?;
+
+    Ok(match symbol.neighbours(&schematic.numbers).as_slice() {
+        [] => format!("`{}` at ({x}, {y}) doesn't touch any numbers", symbol.value),
+        numbers => format!(
+            "`{}` at ({x}, {y}) touches {}",
+            symbol.value,
+            numbers.iter().map(|number| number.value).join(", ")
+        ),
+    })
+}
+
+impl Inspect for Schematic {
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("symbols", "list every symbol and where it is"),
+            ("neighbours <x> <y>", "the numbers touching the symbol at x, y"),
+        ]
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Option<eyre::Result<String>> {
+        Some(match command {
+            "symbols" => Ok(self
+                .symbols
+                .iter()
+                .map(|symbol| format!("`{}` at ({}, {})", symbol.value, symbol.x, symbol.y))
+                .join("\n")),
+            "neighbours" => describe_neighbours(self, args),
+            _ => return None,
+        })
+    }
+}
+
+inventory::submit! {
+    Model {
+        year: 2023,
+        day: 3,
+        load: |input| Ok(Box::new(input.parse::<Schematic>()?)),
+    }
+}
+
+/// the parts parse through the `FromStr` impl, this is the same for anything that wants a function
+pub fn parse(input: &str) -> eyre::Result<Schematic> {
+    input.parse()
+}
+
+#[aoc(year = 2023, day = 3, part = 1)]
+#[tracing::instrument(skip_all)]
 pub fn part_one(schematic: &Schematic) -> eyre::Result<u64> {
     let mut acc = 0;
 
@@ -107,6 +180,8 @@ pub fn part_one(schematic: &Schematic) -> eyre::Result<u64> {
     Ok(acc)
 }
 
+#[aoc(year = 2023, day = 3, part = 2)]
+#[tracing::instrument(skip_all)]
 pub fn part_two(schematic: &Schematic) -> eyre::Result<u64> {
     let mut acc = 0;
 
@@ -121,12 +196,17 @@ pub fn part_two(schematic: &Schematic) -> eyre::Result<u64> {
     Ok(acc)
 }
 
-pub fn main() -> eyre::Result<()> {
-    let input = crate::util::get_input(2023, 3)?;
-    let schematic = input.parse()?;
+#[cfg(test)]
+mod tests {
+    use crate::repl::Inspect;
 
-    tracing::info!("Part One: {}", part_one(&schematic)?);
-    tracing::info!("Part Two: {}", part_two(&schematic)?);
+    #[test]
+    fn test_neighbours_command() {
+        let schematic = super::parse(include_str!("../../examples/2023/03/1.txt")).unwrap();
+        let neighbours = |x, y| schematic.run("neighbours", &[x, y]).unwrap();
 
-    Ok(())
+        assert_eq!(neighbours("3", "1").unwrap(), "`*` at (3, 1) touches 467, 35");
+        assert_eq!(neighbours("3", "4").unwrap(), "`*` at (3, 4) touches 617");
+        assert_eq!(neighbours("0", "0").unwrap_err().to_string(), "no symbol at (0, 0)");
+    }
 }
@@ -1,6 +1,12 @@
 use std::{collections::HashSet, str::FromStr};
 
 use eyre::{Context, ContextCompat};
+use itertools::Itertools;
+
+use crate::{
+    aoc, aoc_parse,
+    repl::{self, Inspect, Model},
+};
 
 #[derive(Debug, Clone)]
 pub struct Card {
@@ -15,6 +21,14 @@ impl Card {
     pub fn points(&self) -> u64 {
         self.player_numbers.intersection(&self.winning_numbers).count() as _
     }
+
+    /// What the card scores in part one, 1 for the first match then doubling for each one after.
+    pub fn score(&self) -> u64 {
+        match self.points() {
+            0 => 0,
+            m => 2u64.pow(m as u32 - 1),
+        }
+    }
 }
 
 impl FromStr for Card {
@@ -55,19 +69,60 @@ impl FromStr for Card {
     }
 }
 
+impl Inspect for Vec<Card> {
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("points <card>", "which of a card's numbers win, and what it scores")]
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Option<eyre::Result<String>> {
+        Some(match command {
+            "points" => repl::arg(args, 0, "card").and_then(|id: u64| {
+                let card = self
+                    .iter()
+                    .find(|card| card.id == id)
+                    .ok_or_else(|| eyre::eyre!("there's no card {id}"))?;
+                let matching = card.player_numbers.intersection(&card.winning_numbers).sorted();
+
+                Ok(match card.points() {
+                    0 => format!("card {id} has no winning numbers, worth 0 points"),
+                    1 => format!(
+                        "card {id} has 1 winning number ({}), worth 1 point",
+                        matching.format(" ")
+                    ),
+                    points => format!(
+                        "card {id} has {points} winning numbers ({}), worth {} points",
+                        matching.format(" "),
+                        card.score()
+                    ),
+                })
+            }),
+            _ => return None,
+        })
+    }
+}
+
+inventory::submit! {
+    Model {
+        year: 2023,
+        day: 4,
+        load: |input| Ok(Box::new(parse(input)?)),
+    }
+}
+
+#[aoc(year = 2023, day = 4, part = 1)]
+#[tracing::instrument(skip_all)]
 pub fn part_one(cards: &[Card]) -> eyre::Result<u64> {
     let mut acc = 0;
 
     for card in cards {
-        acc += match card.points() {
-            0 => 0,
-            m => 2u64.pow(m as u32 - 1),
-        };
+        acc += card.score();
     }
 
     Ok(acc)
 }
 
+#[aoc(year = 2023, day = 4, part = 2)]
+#[tracing::instrument(skip_all)]
 pub fn part_two(cards: &[Card]) -> eyre::Result<u64> {
     let mut counts = cards.iter().map(|_| 1).collect::<Vec<u64>>();
 
@@ -80,15 +135,27 @@ pub fn part_two(cards: &[Card]) -> eyre::Result<u64> {
     Ok(counts.iter().sum())
 }
 
-pub fn main() -> eyre::Result<()> {
-    let input = crate::util::get_input(2023, 4)?;
-    let cards = input
-        .lines()
-        .map(|line| line.parse())
-        .collect::<eyre::Result<Vec<_>>>()?;
-
-    tracing::info!("Part One: {}", part_one(&cards)?);
-    tracing::info!("Part Two: {}", part_two(&cards)?);
+#[aoc_parse(year = 2023, day = 4)]
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> eyre::Result<Vec<Card>> {
+    input.lines().map(|line| line.parse()).collect()
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use crate::repl::Inspect;
+
+    #[test]
+    fn test_points_command() {
+        let cards = super::parse(include_str!("../../examples/2023/04/1.txt")).unwrap();
+        let points = |card| cards.run("points", &[card]).unwrap();
+
+        assert_eq!(
+            points("1").unwrap(),
+            "card 1 has 4 winning numbers (17 48 83 86), worth 8 points"
+        );
+        assert_eq!(points("4").unwrap(), "card 4 has 1 winning number (84), worth 1 point");
+        assert_eq!(points("6").unwrap(), "card 6 has no winning numbers, worth 0 points");
+        assert_eq!(points("7").unwrap_err().to_string(), "there's no card 7");
+    }
 }
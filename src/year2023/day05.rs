@@ -1,11 +1,23 @@
-use std::{ops::Range, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    ops::Range,
+    str::FromStr,
+};
 
 use eyre::ContextCompat;
 use itertools::Itertools;
 
-pub type Point = (isize, isize);
+use crate::{
+    aoc, aoc_parse,
+    repl::{self, Inspect, Model},
+    util::intervals::IntervalSet,
+};
 
-#[derive(Debug)]
+/// every value the composed maps cover, puzzle values are never negative
+const DOMAIN: Range<i64> = 0..i64::MAX;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Map {
     pub source: i64,
     pub destination: i64,
@@ -13,6 +25,24 @@ pub struct Map {
 }
 
 impl Map {
+    /// a map shifting every value in `source` by `delta`
+    pub fn new(source: Range<i64>, delta: i64) -> Self {
+        Self {
+            source: source.start,
+            destination: source.start + delta,
+            range: source.end - source.start,
+        }
+    }
+
+    /// the same map, pointing the other way
+    pub fn inverse(&self) -> Self {
+        Self {
+            source: self.destination,
+            destination: self.source,
+            range: self.range,
+        }
+    }
+
     pub fn source_range(&self) -> Range<i64> {
         self.source..self.source + self.range
     }
@@ -30,11 +60,24 @@ impl Map {
         self.source_range().contains(&value).then(|| value + self.delta())
     }
 
+    /// the value this map would shift to `value`, if it can produce it at all
     pub fn process_backwards(&self, value: i64) -> Option<i64> {
         self.destination_range().contains(&value).then(|| value - self.delta())
     }
 }
 
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} -> {:?} ({:+})",
+            self.source_range(),
+            self.destination_range(),
+            self.delta()
+        )
+    }
+}
+
 impl FromStr for Map {
     type Err = eyre::Error;
 
@@ -50,75 +93,510 @@ impl FromStr for Map {
     }
 }
 
+/// Something stopping a stage from being one to one, which means walking it backwards can give more
+/// than one value, or none at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// both maps cover the same values, going forwards the first one wins
+    OverlappingSources(Map, Map),
+    /// both maps send values to the same place
+    OverlappingDestinations(Map, Map),
+    /// values no map covers are left alone, and some of them land where this map sends values too
+    IdentityCollision { map: Map, values: Range<i64> },
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OverlappingSources(a, b) => write!(f, "sources overlap: {a} and {b}"),
+            Self::OverlappingDestinations(a, b) => write!(f, "destinations overlap: {a} and {b}"),
+            Self::IdentityCollision { map, values } => {
+                write!(f, "{values:?} is left alone by every map, but {map} also ends there")
+            }
+        }
+    }
+}
+
+fn overlaps(a: Range<i64>, b: Range<i64>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+/// Any number of stages squashed into a single stage, as a sorted list of maps covering [`DOMAIN`]
+/// without overlapping. Looking a value up is a binary search instead of a scan through every stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piecewise {
+    segments: Vec<Map>,
+}
+
+impl Piecewise {
+    /// A single stage, where the first map to cover a value wins like in
+    /// [`Almanac::process_forwards`] and the gaps between maps are filled with identity maps.
+    pub fn from_stage(maps: &[Map]) -> Self {
+        let mut claimed = IntervalSet::new();
+        let mut segments = Vec::new();
+
+        for map in maps {
+            let source = IntervalSet::from(map.source_range());
+            for range in source.difference(&claimed).ranges() {
+                segments.push(Map::new(range.clone(), map.delta()));
+            }
+            claimed = claimed.union(&source);
+        }
+
+        for gap in IntervalSet::from(DOMAIN).difference(&claimed).ranges() {
+            segments.push(Map::new(gap.clone(), 0));
+        }
+
+        Self::coalesce(segments)
+    }
+
+    /// sort the segments and join up neighbours that shift by the same amount
+    fn coalesce(mut segments: Vec<Map>) -> Self {
+        segments.sort_unstable_by_key(|segment| segment.source);
+
+        let mut coalesced = Vec::<Map>::with_capacity(segments.len());
+        for segment in segments {
+            match coalesced.last_mut() {
+                Some(last) if last.source_range().end == segment.source && last.delta() == segment.delta() => {
+                    last.range += segment.range
+                }
+                _ => coalesced.push(segment),
+            }
+        }
+
+        Self { segments: coalesced }
+    }
+
+    pub fn segments(&self) -> &[Map] {
+        &self.segments
+    }
+
+    /// map a single value, anything outside [`DOMAIN`] is left alone
+    pub fn apply(&self, value: i64) -> i64 {
+        let index = self.segments.partition_point(|segment| segment.source <= value);
+
+        match index.checked_sub(1).map(|index| &self.segments[index]) {
+            Some(segment) if segment.source_range().contains(&value) => value + segment.delta(),
+            _ => value,
+        }
+    }
+
+    /// this stage followed by `next`, splitting each segment's output wherever `next` changes segment
+    pub fn then(&self, next: &Self) -> Self {
+        let mut segments = Vec::new();
+
+        for segment in self.segments.iter() {
+            let delta = segment.delta();
+            let output = segment.destination_range();
+
+            for piece in next.segments.iter() {
+                let overlap = output.start.max(piece.source)..output.end.min(piece.source_range().end);
+                if !overlap.is_empty() {
+                    segments.push(Map::new(
+                        overlap.start - delta..overlap.end - delta,
+                        delta + piece.delta(),
+                    ));
+                }
+            }
+
+            // anything shifted outside the domain is left alone by `next`
+            for outside in IntervalSet::from(output).difference(&DOMAIN.into()).ranges() {
+                segments.push(Map::new(outside.start - delta..outside.end - delta, delta));
+            }
+        }
+
+        Self::coalesce(segments)
+    }
+}
+
+/// One segment per line, in order.
+impl Display for Piecewise {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.segments.iter().join("\n"))
+    }
+}
+
+/// One `X-to-Y map:` block, moving values from one category to the next.
+#[derive(Debug, Clone)]
+pub struct Stage {
+    pub from: String,
+    pub to: String,
+    pub maps: Vec<Map>,
+}
+
+impl Stage {
+    /// the index of the first map covering the value, which is the one that shifts it
+    pub fn matching(&self, value: i64) -> Option<usize> {
+        self.maps.iter().position(|map| map.source_range().contains(&value))
+    }
+
+    /// the first map covering the value shifts it, otherwise it's left alone
+    pub fn process_forwards(&self, value: i64) -> i64 {
+        self.maps
+            .iter()
+            .find_map(|map| map.process_forwards(value))
+            .unwrap_or(value)
+    }
+
+    /// Every value this stage sends to `value`. Anything a map could have shifted there, or the value
+    /// itself if no map covers it, as long as going forwards really does end up at `value`.
+    pub fn process_backwards(&self, value: i64) -> Vec<i64> {
+        self.maps
+            .iter()
+            .filter_map(|map| map.process_backwards(value))
+            .chain([value])
+            .filter(|candidate| self.process_forwards(*candidate) == value)
+            .sorted_unstable()
+            .dedup()
+            .collect()
+    }
+
+    /// everything that stops this stage from being one to one, empty if it can be walked backwards
+    pub fn problems(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+
+        for (a, b) in self.maps.iter().tuple_combinations() {
+            if overlaps(a.source_range(), b.source_range()) {
+                problems.push(Problem::OverlappingSources(*a, *b));
+            }
+            if overlaps(a.destination_range(), b.destination_range()) {
+                problems.push(Problem::OverlappingDestinations(*a, *b));
+            }
+        }
+
+        let covered = self.maps.iter().map(Map::source_range).collect::<IntervalSet>();
+        let untouched = IntervalSet::from(DOMAIN).difference(&covered);
+        for map in self.maps.iter() {
+            for values in untouched.intersection(&map.destination_range().into()).ranges() {
+                problems.push(Problem::IdentityCollision {
+                    map: *map,
+                    values: values.clone(),
+                });
+            }
+        }
+
+        problems
+    }
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-to-{}", self.from, self.to)
+    }
+}
+
+impl FromStr for Stage {
+    type Err = eyre::Error;
+
+    fn from_str(block: &str) -> eyre::Result<Self> {
+        let (header, body) = block.split_once('\n').context("malformed map")?;
+        let name = header.strip_suffix(" map:").context("malformed header")?;
+        let (from, to) = name
+            .split_once("-to-")
+            .with_context(|| format!("`{name}` isn't an X-to-Y map"))?;
+
+        Ok(Self {
+            from: from.into(),
+            to: to.into(),
+            maps: body.lines().map(str::parse).collect::<eyre::Result<_>>()?,
+        })
+    }
+}
+
+/// Put the stages in order so they form a single chain from `start` to `end`. It's an error for a
+/// stage to appear twice, for a category to lead to two others, for the stages to loop back on
+/// themselves, for the chain to have a gap and for a stage to be left off the chain.
+fn chain(stages: Vec<Stage>, start: &str, end: &str) -> eyre::Result<Vec<Stage>> {
+    let mut by_source = HashMap::new();
+    for (index, stage) in stages.iter().enumerate() {
+        if let Some(previous) = by_source.insert(stage.from.as_str(), index) {
+            let previous = &stages[previous];
+            match previous.to == stage.to {
+                true => eyre::bail!("duplicate stage {stage}"),
+                false => eyre::bail!("{} maps to both {} and {}", stage.from, previous.to, stage.to),
+            }
+        }
+    }
+
+    // each category leads to at most one other, so following them from anywhere either stops or loops
+    for stage in stages.iter() {
+        let mut seen = HashSet::new();
+        let mut category = stage.from.as_str();
+
+        while let Some(index) = by_source.get(category) {
+            eyre::ensure!(seen.insert(category), "the stages loop back round to {category}");
+            category = &stages[*index].to;
+        }
+    }
+
+    let mut order = Vec::new();
+    let mut category = start;
+    while category != end {
+        let index = *by_source
+            .get(category)
+            .with_context(|| format!("missing a stage from {category}, it never reaches {end}"))?;
+
+        order.push(index);
+        category = &stages[index].to;
+    }
+
+    if let Some(stray) = (0..stages.len()).find(|index| !order.contains(index)) {
+        eyre::bail!("{} isn't on the way from {start} to {end}", stages[stray]);
+    }
+
+    let mut stages = stages.into_iter().map(Some).collect::<Vec<_>>();
+    Ok(order.into_iter().filter_map(|index| stages[index].take()).collect())
+}
+
+/// What happened to a value going through one stage, see [`Almanac::trace`].
+#[derive(Debug, Clone)]
+pub struct Step<'a> {
+    pub stage: &'a Stage,
+    pub input: i64,
+    pub output: i64,
+    /// the index of the map that shifted the value, `None` when no map covered it
+    pub matched: Option<usize>,
+}
+
+/// `seed-to-soil: 79 -> 81 via line 2, ...`, counting lines from the first map under the header.
+impl Display for Step<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} -> {}", self.stage, self.input, self.output)?;
+
+        match self.matched {
+            Some(index) => write!(f, " via line {}, {}", index + 1, self.stage.maps[index]),
+            None => write!(f, ", no map covers it"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Almanac {
     pub seeds: Vec<i64>,
-
-    pub seed_to_soil: Vec<Map>,
-    pub soil_to_fertilizer: Vec<Map>,
-    pub fertilizer_to_water: Vec<Map>,
-    pub water_to_light: Vec<Map>,
-    pub light_to_temp: Vec<Map>,
-    pub temp_to_humidity: Vec<Map>,
-    pub humidity_to_location: Vec<Map>,
+    /// every stage in the order a seed goes through them, from seed to location
+    pub stages: Vec<Stage>,
 }
 
 impl Almanac {
+    pub fn stage(&self, name: &str) -> Option<&Stage> {
+        self.stages.iter().find(|stage| stage.to_string() == name)
+    }
+
+    /// the stages leading from one category to another, if `to` comes after `from`
+    fn path(&self, from: &str, to: &str) -> Option<&[Stage]> {
+        // how many stages it takes to reach a category from the start of the chain
+        let position = |category: &str| match self.stages.first().map(|stage| stage.from.as_str()) {
+            Some(first) if first == category => Some(0),
+            _ => Some(self.stages.iter().position(|stage| stage.to == category)? + 1),
+        };
+        let (start, end) = (position(from)?, position(to)?);
+
+        (start <= end).then(|| &self.stages[start..end])
+    }
+
+    /// Convert a value from one category to another, e.g. soil to light. When `to` comes before
+    /// `from` the stages are walked backwards, which gives every value that ends up at `value`.
+    pub fn convert(&self, from: &str, to: &str, value: i64) -> eyre::Result<Vec<i64>> {
+        if let Some(stages) = self.path(from, to) {
+            return Ok(vec![stages
+                .iter()
+                .fold(value, |value, stage| stage.process_forwards(value))]);
+        }
+
+        let stages = self
+            .path(to, from)
+            .with_context(|| format!("there's no way to get from {from} to {to}"))?;
+
+        Ok(preimages(stages, value))
+    }
+
+    /// each stage with a problem, alongside the problem, empty if the almanac can be walked backwards
+    pub fn problems(&self) -> Vec<(&Stage, Problem)> {
+        self.stages
+            .iter()
+            .flat_map(|stage| stage.problems().into_iter().map(move |problem| (stage, problem)))
+            .collect()
+    }
+
     /// for each stage, try all the ranges, if one succeeds, use it to shift
     /// the value, otherwise leave the value alone and move on.
-    pub fn process_forwards(&self, mut index: i64) -> i64 {
-        let process_order = [
-            &self.seed_to_soil,
-            &self.soil_to_fertilizer,
-            &self.fertilizer_to_water,
-            &self.water_to_light,
-            &self.light_to_temp,
-            &self.temp_to_humidity,
-            &self.humidity_to_location,
-        ];
-
-        // walk through each stage, appling it's maps in order
-        // if one of them "hits", set the index to the new value and immediately break off
-        for map in process_order {
-            for m in map {
-                if let Some(i) = m.process_forwards(index) {
-                    index = i;
-                    break;
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn process_forwards(&self, index: i64) -> i64 {
+        self.stages
+            .iter()
+            .fold(index, |index, stage| stage.process_forwards(index))
+    }
+
+    /// every seed that ends up at a location, see [`Stage::process_backwards`]
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn process_backwards(&self, index: i64) -> Vec<i64> {
+        preimages(&self.stages, index)
+    }
+
+    /// every stage a seed goes through on its way to a location, and the map that moved it
+    pub fn trace(&self, seed: i64) -> Vec<Step<'_>> {
+        let mut value = seed;
+
+        self.stages
+            .iter()
+            .map(|stage| {
+                let input = value;
+                value = stage.process_forwards(input);
+
+                Step {
+                    stage,
+                    input,
+                    output: value,
+                    matched: stage.matching(input),
                 }
+            })
+            .collect()
+    }
+
+    /// Push a whole set of values through every stage at once. Each stage splits the set wherever its
+    /// maps' source ranges start and end, shifts the pieces that landed in a map and passes the rest
+    /// through untouched, the same as [`Almanac::process_forwards`] does for a single value.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn process_intervals(&self, values: &IntervalSet) -> IntervalSet {
+        let mut values = values.clone();
+        for stage in self.stages.iter() {
+            let mut remaining = values;
+            let mut mapped = IntervalSet::new();
+
+            // like process_forwards, the first map to cover a value wins
+            for map in stage.maps.iter() {
+                let source = IntervalSet::from(map.source_range());
+                mapped = mapped.union(&remaining.intersection(&source).shift(map.delta()));
+                remaining = remaining.difference(&source);
             }
+
+            values = mapped.union(&remaining);
         }
 
-        index
+        values
     }
 
-    pub fn process_backwards(&self, mut index: i64) -> i64 {
-        let process_order = [
-            &self.humidity_to_location,
-            &self.temp_to_humidity,
-            &self.light_to_temp,
-            &self.water_to_light,
-            &self.fertilizer_to_water,
-            &self.soil_to_fertilizer,
-            &self.seed_to_soil,
-        ];
+    /// every stage squashed into one, taking a seed straight to its location
+    pub fn composed(&self) -> Piecewise {
+        self.stages
+            .iter()
+            .map(|stage| Piecewise::from_stage(&stage.maps))
+            .fold(Piecewise::from_stage(&[]), |composed, stage| composed.then(&stage))
+    }
 
-        // walk through each stage, appling it's maps in order
-        // if one of them "hits", set the index to the new value and immediately break off
-        for map in process_order {
-            for m in map {
-                if let Some(i) = m.process_backwards(index) {
-                    index = i;
-                    break;
-                }
-            }
-        }
+    /// The reverse of [`Almanac::composed`], taking a location straight back to a seed. Values outside
+    /// every destination range are left alone, so it's only right when [`Almanac::problems`] is empty.
+    pub fn composed_inverse(&self) -> Piecewise {
+        self.stages
+            .iter()
+            .rev()
+            .map(|stage| Piecewise::from_stage(&stage.maps.iter().map(Map::inverse).collect::<Vec<_>>()))
+            .fold(Piecewise::from_stage(&[]), |composed, stage| composed.then(&stage))
+    }
+}
+
+/// walk `value` back through the stages, following every branch
+fn preimages(stages: &[Stage], value: i64) -> Vec<i64> {
+    stages.iter().rev().fold(vec![value], |values, stage| {
+        values
+            .into_iter()
+            .flat_map(|value| stage.process_backwards(value))
+            .sorted_unstable()
+            .dedup()
+            .collect()
+    })
+}
+
+fn describe_values(values: &[i64]) -> String {
+    match values {
+        [] => "nothing".into(),
+        values => values.iter().join(" or "),
+    }
+}
+
+/// where a seed ends up, then every step it took to get there
+fn describe_trace(almanac: &Almanac, seed: i64) -> String {
+    let steps = almanac.trace(seed);
+    let location = steps.last().map_or(seed, |step| step.output);
+
+    let mut lines = vec![format!("seed {seed} -> location {location}")];
+    lines.extend(steps.iter().map(|step| format!("  {step}")));
+    lines.join("\n")
+}
+
+/// move the value in `args` between the two categories named before it
+fn describe_conversion(almanac: &Almanac, args: &[&str]) -> eyre::Result<String> {
+    let from = repl::arg::<String>(args, 0, "from")?;
+    let to = repl::arg::<String>(args, 1, "to")?;
+    let value = repl::arg(args, 2, "value")?;
+
+    Ok(format!(
+        "{from} {value} -> {to} {}",
+        describe_values(&almanac.convert(&from, &to, value)?)
+    ))
+}
+
+impl Inspect for Almanac {
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("seeds", "list the seeds"),
+            ("stages", "list the stages in the order a seed goes through them"),
+            ("forward <seed>", "the location a seed ends up at"),
+            (
+                "trace <seed>",
+                "every stage a seed goes through, and the map that moved it",
+            ),
+            ("backward <location>", "every seed that ends up at a location"),
+            ("convert <from> <to> <value>", "move a value between any two categories"),
+            ("maps <stage>", "a stage's maps, e.g. `maps seed-to-soil`"),
+            ("composed", "every stage squashed into one seed to location map"),
+            ("inverse", "the composed map the other way, location to seed"),
+            ("validate", "check every stage can be walked backwards"),
+        ]
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Option<eyre::Result<String>> {
+        Some(match command {
+            "seeds" => Ok(self.seeds.iter().join(" ")),
+            "stages" => Ok(self.stages.iter().join("\n")),
+            "forward" => repl::arg(args, 0, "seed")
+                .map(|seed| format!("seed {seed} -> location {}", self.process_forwards(seed))),
+            "trace" => repl::arg(args, 0, "seed").map(|seed| describe_trace(self, seed)),
+            "backward" => repl::arg(args, 0, "location").map(|location| {
+                format!(
+                    "location {location} -> seed {}",
+                    describe_values(&self.process_backwards(location))
+                )
+            }),
+            "convert" => describe_conversion(self, args),
+            "maps" => repl::arg::<String>(args, 0, "stage").and_then(|name| {
+                let stage = self.stage(&name).with_context(|| format!("unknown stage `{name}`"))?;
+                Ok(stage.maps.iter().join("\n"))
+            }),
+            "composed" => Ok(self.composed().to_string()),
+            "inverse" => Ok(self.composed_inverse().to_string()),
+            "validate" => Ok(match self.problems() {
+                problems if problems.is_empty() => "every stage is one to one".into(),
+                problems => problems
+                    .iter()
+                    .map(|(stage, problem)| format!("{stage}: {problem}"))
+                    .join("\n"),
+            }),
+            _ => return None,
+        })
+    }
 
-        index
+    fn explain(&self, part: u8) -> Option<eyre::Result<String>> {
+        (part == 1).then(|| Ok(self.seeds.iter().map(|seed| describe_trace(self, *seed)).join("\n")))
     }
 }
 
-fn parse_body_to_map(body: &str) -> eyre::Result<Vec<Map>> {
-    body.lines().map(|line| line.parse()).collect::<eyre::Result<Vec<_>>>()
+inventory::submit! {
+    Model {
+        year: 2023,
+        day: 5,
+        load: |input| Ok(Box::new(input.parse::<Almanac>()?)),
+    }
 }
 
 impl FromStr for Almanac {
@@ -138,43 +616,28 @@ impl FromStr for Almanac {
             .map(|chunk| Ok(chunk.parse::<_>()?))
             .collect::<eyre::Result<Vec<_>>>()?;
 
-        let mut seed_to_soil = Default::default();
-        let mut soil_to_fertilizer = Default::default();
-        let mut fertilizer_to_water = Default::default();
-        let mut water_to_light = Default::default();
-        let mut light_to_temp = Default::default();
-        let mut temp_to_humidity = Default::default();
-        let mut humidity_to_location = Default::default();
-
         // now parse each block, skip the seeds
-        for block in input.split("\n\n").skip(1) {
-            let (header, body) = block.split_once('\n').context("malformed map")?;
-
-            match header.strip_suffix(" map:").context("malformed header")? {
-                "seed-to-soil" => seed_to_soil = parse_body_to_map(body)?,
-                "soil-to-fertilizer" => soil_to_fertilizer = parse_body_to_map(body)?,
-                "fertilizer-to-water" => fertilizer_to_water = parse_body_to_map(body)?,
-                "water-to-light" => water_to_light = parse_body_to_map(body)?,
-                "light-to-temperature" => light_to_temp = parse_body_to_map(body)?,
-                "temperature-to-humidity" => temp_to_humidity = parse_body_to_map(body)?,
-                "humidity-to-location" => humidity_to_location = parse_body_to_map(body)?,
-                kind => eyre::bail!("unknown block type: {kind}"),
-            };
-        }
+        let stages = input
+            .split("\n\n")
+            .skip(1)
+            .map(str::parse)
+            .collect::<eyre::Result<Vec<_>>>()?;
 
         Ok(Self {
             seeds,
-            seed_to_soil,
-            soil_to_fertilizer,
-            fertilizer_to_water,
-            water_to_light,
-            light_to_temp,
-            temp_to_humidity,
-            humidity_to_location,
+            stages: chain(stages, "seed", "location")?,
         })
     }
 }
 
+#[aoc_parse(year = 2023, day = 5)]
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> eyre::Result<Almanac> {
+    input.parse()
+}
+
+#[aoc(year = 2023, day = 5, part = 1)]
+#[tracing::instrument(skip_all)]
 pub fn part_one(almanac: &Almanac) -> eyre::Result<i64> {
     almanac
         .seeds
@@ -185,52 +648,174 @@ pub fn part_one(almanac: &Almanac) -> eyre::Result<i64> {
         .context("almanac contained no seeds")
 }
 
+#[aoc(year = 2023, day = 5, part = 1, variant = "composed")]
+#[tracing::instrument(skip_all)]
+pub fn part_one_composed(almanac: &Almanac) -> eyre::Result<i64> {
+    let composed = almanac.composed();
+
+    almanac
+        .seeds
+        .iter()
+        .map(|seed| composed.apply(*seed))
+        .min()
+        .context("almanac contained no seeds")
+}
+
+#[aoc(year = 2023, day = 5, part = 2)]
+#[tracing::instrument(skip_all)]
 pub fn part_two(almanac: &Almanac) -> eyre::Result<i64> {
-    // build list of ranges
-    let ranges = almanac
+    eyre::ensure!(almanac.seeds.len().is_multiple_of(2), "seeds don't pair up into ranges");
+
+    let seeds = almanac
         .seeds
         .iter()
         .tuples()
-        .map(|(start, range)| *start..*start + *range)
-        .sorted_unstable_by_key(|range| range.start)
-        .collect::<Vec<_>>();
-
-    let mut index = 0;
-    let jump = 1000;
-
-    // first, keep incrementing in 10_000s until you find the first index that appears in the seed ranges
-    loop {
-        let seed = almanac.process_backwards(index);
-        if ranges.iter().any(|range| range.contains(&seed)) {
-            // ok subtract if we're above 0, and break out
-            if index > 0 {
-                index -= jump;
+        .map(|(start, length)| *start..*start + *length)
+        .collect::<IntervalSet>();
+
+    almanac
+        .process_intervals(&seeds)
+        .min()
+        .context("no seed ranges to map to a location")
+}
+
+#[aoc(year = 2023, day = 5, part = 2, variant = "forwards")]
+#[tracing::instrument(skip_all)]
+pub fn part_two_forwards(almanac: &Almanac) -> eyre::Result<i64> {
+    // push every single seed through, slow but it doesn't trust process_backwards
+    let mut lowest = None;
+
+    for (start, length) in almanac.seeds.iter().tuples() {
+        for seed in *start..*start + *length {
+            if seed % 100_000 == 0 {
+                crate::runner::check_cancelled()?;
             }
-            break;
+
+            let location = almanac.process_forwards(seed);
+            lowest = Some(lowest.map_or(location, |lowest: i64| lowest.min(location)));
         }
+    }
 
-        // didn't find it? Jump forwards a large chunk of elements
-        index += jump;
+    lowest.context("almanac contained no seed ranges")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Almanac, Map, Problem, Stage};
+
+    fn example() -> String {
+        crate::examples::load(2023, 5, 1).unwrap()
     }
 
-    // now go forward and search in fine detail
-    loop {
-        let seed = almanac.process_backwards(index);
-        if ranges.iter().any(|range| range.contains(&seed)) {
-            break;
-        }
-        index += 1
+    #[test]
+    fn test_convert_between_categories() {
+        let almanac = example().parse::<Almanac>().unwrap();
+
+        assert_eq!(almanac.convert("seed", "location", 79).unwrap(), vec![82]);
+        assert_eq!(almanac.convert("soil", "light", 81).unwrap(), vec![74]);
+        assert_eq!(almanac.convert("light", "soil", 74).unwrap(), vec![81]);
+        assert_eq!(almanac.convert("water", "water", 5).unwrap(), vec![5]);
+        assert_eq!(almanac.convert("seed", "seed", 5).unwrap(), vec![5]);
+        assert_eq!(almanac.convert("location", "seed", 82).unwrap(), vec![79]);
+        assert!(almanac.convert("soil", "gold", 81).is_err());
     }
 
-    Ok(index)
-}
+    #[test]
+    fn test_broken_stage_graphs() {
+        let error = |input: String| input.parse::<Almanac>().unwrap_err().to_string();
+        let example = example();
+
+        let duplicate = format!("{}\n\nseed-to-soil map:\n1 2 3", example.trim_end());
+        assert_eq!(error(duplicate), "duplicate stage seed-to-soil");
 
-pub fn main() -> eyre::Result<()> {
-    let input = crate::util::get_input(2023, 5)?;
-    let almanac = input.parse::<Almanac>()?;
+        let branch = format!("{}\n\nseed-to-water map:\n1 2 3", example.trim_end());
+        assert_eq!(error(branch), "seed maps to both soil and water");
 
-    tracing::info!("Part One: {}", part_one(&almanac)?);
-    tracing::info!("Part Two: {}", part_two(&almanac)?);
+        let cycle = format!("{}\n\nlocation-to-seed map:\n1 2 3", example.trim_end());
+        assert!(error(cycle).starts_with("the stages loop back round"));
 
-    Ok(())
+        let stray = format!("{}\n\nfoo-to-bar map:\n1 2 3", example.trim_end());
+        assert_eq!(error(stray), "foo-to-bar isn't on the way from seed to location");
+
+        let (before, after) = example.split_once("water-to-light map:").unwrap();
+        let gap = format!("{before}{}", after.split_once("\n\n").unwrap().1);
+        assert_eq!(error(gap), "missing a stage from water, it never reaches location");
+    }
+
+    #[test]
+    fn test_trace() {
+        let almanac = example().parse::<Almanac>().unwrap();
+        let steps = almanac.trace(79);
+
+        let values = steps.iter().map(|step| step.output).collect::<Vec<_>>();
+        assert_eq!(values, vec![81, 81, 81, 74, 78, 78, 82]);
+        assert!(steps.windows(2).all(|pair| pair[0].output == pair[1].input));
+
+        // 50 52 48 is the second line of seed-to-soil, fertilizer 81 isn't covered by any map
+        assert_eq!(steps[0].matched, Some(1));
+        assert_eq!(steps[2].matched, None);
+        assert_eq!(
+            steps[0].to_string(),
+            "seed-to-soil: 79 -> 81 via line 2, 50..98 -> 52..100 (+2)"
+        );
+        assert_eq!(steps[2].to_string(), "fertilizer-to-water: 81 -> 81, no map covers it");
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(example().parse::<Almanac>().unwrap().problems().is_empty());
+
+        // 98 and 99 move down onto 50 and 51, which are also left where they are
+        let collision = "a-to-b map:\n50 98 2".parse::<Stage>().unwrap();
+        let map = Map::new(98..100, -48);
+        assert_eq!(
+            collision.problems(),
+            vec![Problem::IdentityCollision { map, values: 50..52 }]
+        );
+        assert_eq!(collision.process_backwards(50), vec![50, 98]);
+        assert!(collision.process_backwards(98).is_empty());
+
+        // the second map only gets 15 and 16, so 102 can only come from itself
+        let sources = "a-to-b map:\n0 10 5\n100 12 5".parse::<Stage>().unwrap();
+        assert_eq!(
+            sources.problems()[0],
+            Problem::OverlappingSources(Map::new(10..15, -10), Map::new(12..17, 88))
+        );
+        assert_eq!(sources.process_backwards(102), vec![102]);
+        assert_eq!(sources.process_backwards(103), vec![15, 103]);
+
+        let destinations = "a-to-b map:\n5 0 5\n5 10 5".parse::<Stage>().unwrap();
+        assert!(matches!(
+            destinations.problems()[0],
+            Problem::OverlappingDestinations(..)
+        ));
+        assert_eq!(destinations.process_backwards(6), vec![1, 6, 11]);
+    }
+
+    #[test]
+    fn test_composition_matches_stage_by_stage() {
+        let almanac = crate::examples::load(2023, 5, 1).unwrap().parse::<Almanac>().unwrap();
+        let (composed, inverse) = (almanac.composed(), almanac.composed_inverse());
+
+        // segments are sorted, don't overlap and leave no gaps
+        for segments in [composed.segments(), inverse.segments()] {
+            assert_eq!(segments.first().unwrap().source, 0);
+            assert!(segments
+                .windows(2)
+                .all(|pair| pair[0].source_range().end == pair[1].source));
+        }
+
+        for value in 0..200 {
+            assert_eq!(
+                composed.apply(value),
+                almanac.process_forwards(value),
+                "forwards {value}"
+            );
+            assert_eq!(
+                vec![inverse.apply(value)],
+                almanac.process_backwards(value),
+                "backwards {value}"
+            );
+        }
+    }
 }
@@ -1,5 +1,9 @@
+use std::ops::RangeInclusive;
+
 use eyre::ContextCompat;
 
+use crate::aoc;
+
 #[derive(Debug)]
 pub struct Race {
     pub time: i64,
@@ -8,36 +12,111 @@ pub struct Race {
 
 impl Race {
     /// Provide a time that a boats button is held for. Returns a distance the
-    /// boat will travel in the allotted race time.
-    pub fn distance_travelled(&self, wait: i64) -> i64 {
-        if !(1..self.distance).contains(&wait) {
+    /// boat will travel in the allotted race time, as an `i128` so long races can't overflow.
+    /// Holding the button for none or all of the race goes nowhere.
+    pub fn distance_travelled(&self, wait: i64) -> i128 {
+        if !(1..self.time).contains(&wait) {
             return 0;
         }
 
         let time_remaining = self.time - wait;
-        time_remaining * wait
+        i128::from(time_remaining) * i128::from(wait)
+    }
+
+    fn wins(&self, wait: i64) -> bool {
+        self.distance_travelled(wait) > i128::from(self.distance)
     }
 
     /// return a Vec of all the winning wait durations
     pub fn find_all_winning_times(&self) -> Vec<i64> {
         // don't include 0 or self.time
-        (1..self.time)
-            .filter(|wait| self.distance_travelled(*wait) > self.distance)
-            .collect::<Vec<_>>()
+        (1..self.time).filter(|wait| self.wins(*wait)).collect::<Vec<_>>()
+    }
+
+    /// count the winning waits by walking in from each end to the first win, everything between wins too
+    pub fn count_winning_times(&self) -> i64 {
+        let wins = |wait: &i64| self.wins(*wait);
+
+        match ((1..self.time).find(wins), (1..self.time).rev().find(wins)) {
+            (Some(lowest), Some(highest)) => highest - lowest + 1,
+            _ => 0,
+        }
+    }
+
+    /// The lowest and highest winning waits, worked out from where `wait * (time - wait) = distance`
+    /// instead of trying them. Everything is done exactly in `i128`, so there's no float rounding to
+    /// second guess and `time * time` can't overflow even for an `i64::MAX` long race.
+    pub fn winning_waits(&self) -> Option<RangeInclusive<i64>> {
+        let (time, distance) = (i128::from(self.time), i128::from(self.distance));
+        let wins = |wait: i128| wait * (time - wait) > distance;
+
+        let discriminant = time * time - 4 * distance;
+        if discriminant < 0 {
+            return None;
+        }
+
+        // the roots are (time ± √discriminant) / 2, rounding the square root down means this starts at
+        // most one below the first win, and nothing before it wins
+        let mut lowest = ((time - discriminant.isqrt()) / 2).max(1);
+        while !wins(lowest) {
+            // past the halfway point it only gets worse
+            if lowest >= time / 2 {
+                return None;
+            }
+            lowest += 1;
+        }
+
+        // the distance is symmetric about time / 2
+        let highest = time - lowest;
+        Some(i64::try_from(lowest).ok()?..=i64::try_from(highest).ok()?)
+    }
+
+    /// how many waits win, without trying any of them
+    pub fn count_winning_waits(&self) -> i64 {
+        self.winning_waits().map_or(0, |waits| waits.end() - waits.start() + 1)
     }
 }
 
+#[aoc(year = 2023, day = 6, part = 1, parse = parse_part_one)]
+#[tracing::instrument(skip_all)]
 pub fn part_one(races: &[Race]) -> eyre::Result<i64> {
+    Ok(races.iter().map(Race::count_winning_waits).product())
+}
+
+#[aoc(year = 2023, day = 6, part = 1, parse = parse_part_one, variant = "brute")]
+#[tracing::instrument(skip_all)]
+pub fn part_one_brute(races: &[Race]) -> eyre::Result<i64> {
     Ok(races
         .iter()
         .map(|race| race.find_all_winning_times().len() as i64)
         .product())
 }
 
+#[aoc(year = 2023, day = 6, part = 1, parse = parse_part_one, variant = "scan")]
+#[tracing::instrument(skip_all)]
+pub fn part_one_scan(races: &[Race]) -> eyre::Result<i64> {
+    Ok(races.iter().map(Race::count_winning_times).product())
+}
+
+#[aoc(year = 2023, day = 6, part = 2, parse = parse_part_two)]
+#[tracing::instrument(skip_all)]
 pub fn part_two(race: &Race) -> eyre::Result<i64> {
+    Ok(race.count_winning_waits())
+}
+
+#[aoc(year = 2023, day = 6, part = 2, parse = parse_part_two, variant = "brute")]
+#[tracing::instrument(skip_all)]
+pub fn part_two_brute(race: &Race) -> eyre::Result<i64> {
     Ok(race.find_all_winning_times().len() as _)
 }
 
+#[aoc(year = 2023, day = 6, part = 2, parse = parse_part_two, variant = "scan")]
+#[tracing::instrument(skip_all)]
+pub fn part_two_scan(race: &Race) -> eyre::Result<i64> {
+    Ok(race.count_winning_times())
+}
+
+#[tracing::instrument(skip_all)]
 fn parse_part_one(input: &str) -> eyre::Result<Vec<Race>> {
     let mut times = Vec::new();
     let mut distances = Vec::new();
@@ -65,6 +144,7 @@ fn parse_part_one(input: &str) -> eyre::Result<Vec<Race>> {
         .collect::<Vec<_>>())
 }
 
+#[tracing::instrument(skip_all)]
 fn parse_part_two(input: &str) -> eyre::Result<Race> {
     let mut time = 0;
     let mut distance = 0;
@@ -79,13 +159,116 @@ fn parse_part_two(input: &str) -> eyre::Result<Race> {
 
     Ok(Race { time, distance })
 }
-pub fn main() -> eyre::Result<()> {
-    let input = crate::util::get_input(2023, 6)?;
-    let races = parse_part_one(&input)?;
-    let race = parse_part_two(&input)?;
 
-    tracing::info!("Part One: {}", part_one(&races)?);
-    tracing::info!("Part Two: {}", part_two(&race)?);
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
 
-    Ok(())
+    use super::Race;
+
+    /// Count the winning waits a different way to [`Race::winning_waits`]. With `k = 2 * wait - time`
+    /// a wait wins when `k * k < time * time - 4 * distance`, so count the `k` with the same parity as
+    /// `time` up to the biggest `m` where `m * m` is still under that.
+    fn reference_count(race: &Race) -> i64 {
+        let (time, distance) = (i128::from(race.time), i128::from(race.distance));
+        let discriminant = time * time - 4 * distance;
+        if discriminant <= 0 {
+            return 0;
+        }
+
+        let m = (discriminant - 1).isqrt();
+        let count = match time % 2 {
+            0 => 2 * (m / 2) + 1,
+            _ => 2 * ((m + 1) / 2),
+        };
+        count as i64
+    }
+
+    /// every way of counting, brute force first
+    fn counts(race: &Race) -> [i64; 4] {
+        [
+            race.find_all_winning_times().len() as i64,
+            race.count_winning_times(),
+            race.count_winning_waits(),
+            reference_count(race),
+        ]
+    }
+
+    #[test]
+    fn test_winning_waits() {
+        let race = |time, distance| Race { time, distance };
+
+        assert_eq!(race(7, 9).winning_waits(), Some(2..=5));
+        assert_eq!(race(15, 40).winning_waits(), Some(4..=11));
+        // 10 and 20 exactly tie the record, which isn't a win
+        assert_eq!(race(30, 200).winning_waits(), Some(11..=19));
+        assert_eq!(race(71530, 940200).count_winning_waits(), 71503);
+
+        // 2 * 2 only ties, and nothing gets close to 5
+        assert_eq!(race(4, 4).winning_waits(), None);
+        assert_eq!(race(4, 5).winning_waits(), None);
+
+        // time * time is way past i64::MAX, holding for 1 falls just short and 2 is plenty
+        let long = race(i64::MAX, i64::MAX);
+        assert_eq!(long.winning_waits(), Some(2..=i64::MAX - 2));
+        assert_eq!(long.count_winning_waits(), i64::MAX - 3);
+    }
+
+    #[test]
+    fn test_distance_travelled() {
+        // the record used to bound the wait, so 5 counted as too long
+        let race = Race { time: 10, distance: 3 };
+        assert_eq!(race.distance_travelled(5), 25);
+        assert_eq!(race.distance_travelled(9), 9);
+        assert_eq!(race.distance_travelled(10), 0);
+        assert_eq!(counts(&race), [9; 4]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_counts_agree(time in 0i64..300, distance in 0i64..25_000) {
+            let race = Race { time, distance };
+            let counts = counts(&race);
+            let [brute, ..] = counts;
+            prop_assert_eq!(counts, [brute; 4], "{:?}", race);
+        }
+
+        #[test]
+        fn test_ties_at_the_record((time, wait) in (0i64..300).prop_flat_map(|time| (Just(time), 0..=time))) {
+            // the discriminant is (time - 2 * wait)², so a perfect square, and `wait` only ties
+            let race = Race { time, distance: wait * (time - wait) };
+            let counts = counts(&race);
+            let [brute, ..] = counts;
+
+            prop_assert_eq!(counts, [brute; 4], "{:?}", race);
+            prop_assert!(!race.find_all_winning_times().contains(&wait));
+        }
+
+        #[test]
+        fn test_zero_winners(time in 0i64..300, extra in 0i64..100) {
+            // even the best wait, half the race, can't beat this
+            let race = Race { time, distance: (time / 2) * (time - time / 2) + extra };
+            prop_assert_eq!(counts(&race), [0; 4]);
+        }
+
+        #[test]
+        fn test_long_races(
+            (time, wait) in (0i64..=3_037_000_499).prop_flat_map(|time| (Just(time), 0..=time)),
+            offset in -2i64..=2,
+        ) {
+            // too long to brute force, so check the closed form is right at both ends instead, any
+            // time up to √i64::MAX keeps every record in range
+            let distance = wait * (time - wait) + offset;
+            prop_assume!(distance >= 0);
+            let race = Race { time, distance };
+
+            prop_assert_eq!(race.count_winning_waits(), reference_count(&race));
+            if let Some(waits) = race.winning_waits() {
+                let (lowest, highest) = (*waits.start(), *waits.end());
+                for (wait, wins) in [(lowest - 1, false), (lowest, true), (highest, true), (highest + 1, false)] {
+                    prop_assert_eq!(race.wins(wait), wins, "{:?} {}", race, wait);
+                }
+            }
+        }
+    }
 }
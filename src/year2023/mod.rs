@@ -4,14 +4,3 @@ pub mod day03;
 pub mod day04;
 pub mod day05;
 pub mod day06;
-
-pub fn main() -> eyre::Result<()> {
-    day01::main()?;
-    day02::main()?;
-    day03::main()?;
-    day04::main()?;
-    day05::main()?;
-    day06::main()?;
-
-    Ok(())
-}
@@ -0,0 +1,41 @@
+//! Runs every example under `examples/` through each implementation of its day's parts and checks the
+//! answers listed next to it, see `narigama_aoc2023::examples` for the layout.
+
+use narigama_aoc2023::examples;
+
+#[test]
+fn test_examples() {
+    let mut checked = 0;
+    let mut failures = Vec::new();
+
+    for (year, day) in examples::days().unwrap() {
+        let solutions = narigama_aoc2023::find_day(year, day).unwrap();
+
+        for expectation in examples::expectations(year, day).unwrap() {
+            let input = examples::load(year, day, expectation.example).unwrap();
+
+            for solution in solutions.variants(expectation.part) {
+                let name = format!(
+                    "{year}/{day:0>2} example {} part {} ({})",
+                    expectation.example,
+                    expectation.part,
+                    solution.name()
+                );
+
+                match (solution.solver)(&input) {
+                    Ok(answer) if answer == expectation.answer => checked += 1,
+                    Ok(answer) => failures.push(format!("{name}: expected {}, got {answer}", expectation.answer)),
+                    Err(error) => failures.push(format!("{name}: {error:#}")),
+                }
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} examples failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+    assert!(checked > 0, "no examples found");
+}
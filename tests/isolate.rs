@@ -0,0 +1,68 @@
+//! Drives the `aoc` binary with `--isolate`, so each part runs in a child process that the runner has to
+//! kill, or pick a panic or a failure out of, by itself.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+
+/// solve one part of a day with `input` on stdin, returning the part's json record
+fn solve(day: u64, part: u8, input: &str, args: &[&str]) -> serde_json::Value {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["--isolate", "--format", "json", "--input", "-"])
+        .args(["--day", &day.to_string(), "--part", &part.to_string()])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_solved() {
+    let record = solve(4, 1, "Card 1: 1 | 1\n", &["--memory-limit", "64"]);
+    assert_eq!(record["status"], "ok");
+    assert_eq!(record["answer"], "1");
+}
+
+#[test]
+fn test_timed_out() {
+    // every symbol checks every number, so this takes minutes unless the child is killed
+    let input = format!("{}\n", "1*".repeat(50)).repeat(1_000);
+
+    let start = Instant::now();
+    let record = solve(3, 1, &input, &["--timeout", "0.5"]);
+
+    assert_eq!(record["status"], "timeout");
+    assert!(start.elapsed() < Duration::from_secs(30), "took {:?}", start.elapsed());
+}
+
+#[test]
+fn test_panicked() {
+    // the last card wins copies of cards past the end of the table
+    let record = solve(4, 2, "Card 1: 1 | 1\n", &[]);
+    assert_eq!(record["status"], "panic");
+    assert_eq!(record["error"], "index out of bounds: the len is 1 but the index is 1");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_out_of_memory() {
+    // every number takes 20 times the space its two characters did
+    let input = format!("{}\n", "1.".repeat(50)).repeat(200_000);
+
+    let record = solve(3, 1, &input, &["--memory-limit", "64"]);
+    assert_eq!(record["status"], "error");
+    assert!(
+        record["error"].as_str().unwrap().contains("memory allocation"),
+        "{}",
+        record["error"]
+    );
+}